@@ -1,150 +1,12 @@
+mod intcode;
+
 use std::collections::HashMap;
-use std::rc::Rc;
-use std::cell::{Cell, RefCell};
+use std::error::Error;
 //use std::iter::FromIterator;
-
-fn interpret(mem: &mut Vec<i64>,
-             read_input: &mut dyn FnMut() -> i64,
-             write_output: &mut dyn FnMut(i64)) {
-    #[derive(PartialEq, Debug)]
-    enum AddrMode {
-        POSITION,
-        IMMEDIATE,
-        RELATIVE
-    }
-
-    fn read_direct(mem: &[i64], pos: usize) -> i64 {
-        if pos >= mem.len() {
-            0
-        }
-        else {
-            mem[pos]
-        }
-    }
-    fn mem_follow_mode(val: i64, mem: &[i64], relbase: usize, mode: AddrMode)
-                    -> i64 {
-        match mode {
-            AddrMode::POSITION => read_direct(mem, val as usize),
-            AddrMode::IMMEDIATE => val,
-            AddrMode::RELATIVE => read_direct(mem, (relbase as i64 + val) as usize),
-        }
-    }
-    fn mem_read(mem: &[i64], pos: usize, relbase: usize, mode: AddrMode) -> i64 {
-        mem_follow_mode(read_direct(mem, pos), mem, relbase, mode)
-    }
-
-    fn decode_mode(digit: i64) -> AddrMode {
-        match digit {
-            0 => AddrMode::POSITION,
-            1 => AddrMode::IMMEDIATE,
-            2 => AddrMode::RELATIVE,
-            other => panic!("invalid opcode {}", other),
-        }
-    }
-
-    fn decode_next(mem: &mut [i64], pos: usize, relbase: usize, modes: i64) -> (i64, i64) {
-        (mem_read(mem, pos, relbase, decode_mode(modes % 10)),
-         modes / 10)
-    }
-    fn decode_operands_1(mem: &mut [i64], pos: usize, relbase: usize) -> (i64, i64) {
-        let modes = mem[pos] / 100;
-        decode_next(mem, pos + 1, relbase, modes)
-    }
-    fn decode_operands_2(mem: &mut [i64], pos: usize, relbase: usize) -> (i64, i64, i64) {
-        let modes = mem[pos] / 100;
-        let (val1, modes) = decode_next(mem, pos + 1, relbase, modes);
-        let (val2, modes) = decode_next(mem, pos + 2, relbase, modes);
-        (val1, val2, modes)
-    }
-
-    fn mem_write(mem: &mut Vec<i64>, relbase: usize,
-                 raw_addr: i64, mode: AddrMode, val: i64) {
-        assert_ne!(mode, AddrMode::IMMEDIATE);
-        let pos = if let AddrMode::RELATIVE = mode {
-            (raw_addr as isize + relbase as isize) as usize
-        }
-        else {raw_addr as usize};
-        if pos >= mem.len() {
-            mem.extend(std::iter::repeat(0).take(pos - mem.len() + 1));
-        }
-        mem[pos] = val;
-    }
-
-    let mut pos = 0usize;
-    let mut relbase = 0usize;
-
-    while mem[pos] != 99 {
-        match mem[pos] % 100 {
-            1 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = op1.checked_add(op2)
-                    .expect(&format!("overflow {}+{}", op1, op2));
-                mem_write(mem, relbase, mem[pos + 3], decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            2 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = op1.checked_mul(op2)
-                    .expect(&format!("overflow {}*{}", op1, op2));
-                mem_write(mem, relbase, mem[pos + 3], decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            3 => {
-                let write_mode = decode_mode(mem[pos] / 100);
-                let val = read_input();
-                mem_write(mem, relbase, mem[pos + 1], write_mode, val);
-                pos += 2;
-            }
-            4 => {
-                let (val, modes) = decode_operands_1(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                write_output(val);
-                pos += 2;
-            }
-            5 => {
-                let (op1, op2, modes) = decode_operands_2(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                if op1 != 0 {
-                    pos = op2 as usize;
-                }
-                else {
-                    pos += 3;
-                }
-            }
-            6 => {
-                let (op1, op2, modes) = decode_operands_2(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                if op1 == 0 {
-                    pos = op2 as usize;
-                }
-                else {
-                    pos += 3;
-                }
-            }
-            7 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = if op1 < op2 {1} else {0};
-                mem_write(mem, relbase, mem[pos + 3],
-                          decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            8 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = if op1 == op2 {1} else {0};
-                mem_write(mem, relbase, mem[pos + 3],
-                          decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            9 => {
-                let (val, modes) = decode_operands_1(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                relbase = (relbase as isize + val as isize) as usize;
-                pos += 2;
-            }
-            other => panic!("invalid instruction {}", other),
-        };
-    }
-}
+use intcode::{Machine, Event};
+use intcode::device::{self, Device, FrameHandler, Framer, Tee};
+use intcode::replay::{replay, Io, Recording, ReplayError};
+use intcode::load;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Tile {
@@ -206,73 +68,124 @@ fn locate(screen: &HashMap<(i64, i64), Tile>, tile: Tile) -> (i64, i64) {
 //     std::thread::sleep(std::time::Duration::from_micros(500));
 // }
 
-fn beat_pong(program: &[i64]) -> i64 {
-    let screen = Rc::new(RefCell::new(HashMap::new()));
-    let score1 = Rc::new(Cell::new(None));
-    {
-        let mut score = 0;
-        let (mut x, mut y) = (None, None);
-        let mut output = {
-            let screen = Rc::clone(&screen);
-            let score1 = Rc::clone(&score1);
-            move |val| {
-                match (x, y) {
-                (None, None) => {
-                    x = Some(val);
-                    y = None;
-                }
-                (Some(_), None) => {
-                    y = Some(val);
-                }
-                (Some(xval), Some(yval)) => {
-                    if (xval, yval) != (-1, 0) {
-                        screen.borrow_mut().insert((xval, yval), Tile::decode(val));
-                    }
-                    else {
-                        score = val;
-                        if all_blocks_broken(&*screen.borrow()) {
-                            score1.set(Some(score));
-                        }
-                    }
-                    x = None;
-                    y = None;
-                    //display(&*screen.borrow(), score);
-                }
-                _ => unreachable!(),
+// The game speaks in (x, y, tile) triplets, with (-1, 0, score) for the
+// score, and reads the joystick position.
+struct Pong {
+    screen: HashMap<(i64, i64), Tile>,
+    // set once the last block is broken
+    final_score: Option<i64>,
+}
+
+impl Pong {
+    fn new() -> Pong {
+        Pong { screen: HashMap::new(), final_score: None }
+    }
+}
+
+impl FrameHandler for Pong {
+    fn read(&mut self) -> Option<i64> {
+        let paddle_pos = locate(&self.screen, Tile::PADDLE).0;
+        let ball_pos = locate(&self.screen, Tile::BALL).0;
+        Some(if ball_pos < paddle_pos { -1 }
+             else if ball_pos > paddle_pos { 1 }
+             else { 0 })
+    }
+
+    fn frame(&mut self, frame: &[i64]) {
+        match *frame {
+            [-1, 0, score] => {
+                if all_blocks_broken(&self.screen) {
+                    self.final_score = Some(score);
                 }
+                //display(&self.screen, score);
             }
-        };
-
-        let mut input = {
-            let screen = Rc::clone(&screen);
-            move || {
-                let screen = &*screen.borrow();
-                let paddle_pos = locate(screen, Tile::PADDLE).0;
-                let ball_pos = locate(screen, Tile::BALL).0;
-                if ball_pos < paddle_pos { -1 }
-                else if ball_pos > paddle_pos { 1 }
-                else { 0 }
+            [x, y, tile] => {
+                self.screen.insert((x, y), Tile::decode(tile));
             }
-        };
-
-        interpret(&mut program.to_vec(),
-                  &mut input,
-                  &mut output);
+            _ => unreachable!(),
+        }
     }
-    score1.get().unwrap()
 }
 
-fn read_prog(input: &[u8]) -> Vec<i64> {
-    let input = std::str::from_utf8(input).unwrap();
-    let mut ret = vec![];
-    for tok in input.split(",") {
-        ret.push(tok.trim().parse().unwrap());
+fn beat_pong(program: &[i64], recording: &mut Recording) -> i64 {
+    let mut pong = Tee::new(Framer::new(3, Pong::new()), |io| recording.events.push(io));
+    device::run(&mut Machine::new(program), &mut pong).unwrap();
+    pong.inner.handler.final_score.unwrap()
+}
+
+// Final score of a recorded game.
+fn replay_pong(program: &[i64], recording: &Recording) -> Result<i64, ReplayError> {
+    let mut pong = Framer::new(3, Pong::new());
+    replay(&mut Machine::new(program), recording, &mut |val| pong.write(val))?;
+    Ok(pong.handler.final_score.unwrap())
+}
+
+fn run_tests(program: &[i64]) {
+    let mut recording = Recording::new();
+    let score = beat_pong(program, &mut recording);
+    assert_eq!(replay_pong(program, &recording), Ok(score));
+    assert_eq!(recording.to_string().parse(), Ok(recording.clone()));
+    assert_eq!("intcode io 1\nin 1\nout x\n".parse::<Recording>().unwrap_err().line, 3);
+
+    // a changed output is reported where it is
+    let mut changed = recording.clone();
+    let index = changed.events.iter().rposition(|io| match *io {
+        Io::Out(_) => true,
+        Io::In(_) => false,
+    }).unwrap();
+    changed.events[index] = Io::Out(-5);
+    match replay_pong(program, &changed) {
+        Err(ReplayError::Mismatch { index: i, expected: Some(Io::Out(-5)), actual: Event::Output(_) })
+            if i == index => (),
+        other => panic!("unexpected replay result {:?}", other),
+    }
+
+    // a different move shows up in a later output
+    let mut changed = recording.clone();
+    let index = changed.events.iter().position(|io| match *io {
+        Io::Out(_) => false,
+        Io::In(_) => true,
+    }).unwrap();
+    changed.events[index] = match changed.events[index] {
+        Io::In(1) => Io::In(-1),
+        _ => Io::In(1),
+    };
+    match replay_pong(program, &changed) {
+        Err(ReplayError::Mismatch { index: i, .. }) => assert!(i > index),
+        other => panic!("unexpected replay result {:?}", other),
+    }
+
+    let mut changed = recording.clone();
+    changed.events.truncate(10);
+    match replay_pong(program, &changed) {
+        Err(ReplayError::Mismatch { index: 10, expected: None, .. }) => (),
+        other => panic!("unexpected replay result {:?}", other),
     }
-    ret
 }
 
-fn main() {
-    let mut prog = read_prog(include_bytes!("13.input"));
+const USAGE: &str = "usage: 13b [--record FILE | --replay FILE]";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut prog = load::from_bytes(include_bytes!("13.input")).unwrap();
     prog[0] = 2;
-    println!("{}", beat_pong(&prog));
+    run_tests(&prog);
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.len() {
+        0 => println!("{}", beat_pong(&prog, &mut Recording::new())),
+        2 if args[0] == "--record" => {
+            let mut recording = Recording::new();
+            println!("{}", beat_pong(&prog, &mut recording));
+            recording.save_file(&args[1])?;
+        }
+        2 if args[0] == "--replay" => {
+            let recording = Recording::load_file(&args[1])?;
+            println!("{}", replay_pong(&prog, &recording)?);
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+    Ok(())
 }
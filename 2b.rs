@@ -1,8 +1,11 @@
-use std::io;
-use std::io::prelude::*;
+mod intcode;
+
+use std::collections::BTreeMap;
+use std::fmt;
 use std::error::Error;
+use intcode::load;
 
-fn interpret(mem: &mut [u32]) -> &mut [u32] {
+fn interpret(mem: &mut [u32]) -> Result<(), String> {
     let mut pos = 0;
     while mem[pos] != 99 {
         let op1 = mem[mem[pos + 1] as usize];
@@ -10,41 +13,272 @@ fn interpret(mem: &mut [u32]) -> &mut [u32] {
         let ind_result = mem[pos + 3] as usize;
         match mem[pos] {
             1 => mem[ind_result] = op1.checked_add(op2)
-                .expect(&format!("overflow {}+{}", op1, op2)),
+                .ok_or_else(|| format!("overflow {}+{}", op1, op2))?,
             2 => mem[ind_result] = op1.checked_mul(op2)
-                .expect(&format!("overflow {}*{}", op1, op2)),
-            other => panic!("invalid opcode {}", other),
+                .ok_or_else(|| format!("overflow {}*{}", op1, op2))?,
+            other => return Err(format!("invalid opcode {}", other)),
+        };
+        pos += 4;
+    }
+    Ok(())
+}
+
+// Polynomial in the noun and verb, mapping (noun exponent, verb exponent) to
+// the coefficient.  The program only adds and multiplies, so coefficients
+// are never negative, and zero ones are not stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Poly(BTreeMap<(u32, u32), u64>);
+
+impl Poly {
+    fn constant(val: u32) -> Poly {
+        let mut terms = BTreeMap::new();
+        if val != 0 {
+            terms.insert((0, 0), val as u64);
+        }
+        Poly(terms)
+    }
+
+    fn var(noun_exp: u32, verb_exp: u32) -> Poly {
+        let mut terms = BTreeMap::new();
+        terms.insert((noun_exp, verb_exp), 1);
+        Poly(terms)
+    }
+
+    fn as_constant(&self) -> Option<u32> {
+        match self.0.iter().next() {
+            None => Some(0),
+            Some((&(0, 0), &coeff)) if self.0.len() == 1 && coeff <= u32::MAX as u64 =>
+                Some(coeff as u32),
+            _ => None,
+        }
+    }
+
+    // Whether the value changes with the verb once the noun is fixed.  A
+    // zero noun drops every term with a power of it.
+    fn depends_on_verb(&self, noun: u32) -> bool {
+        self.0.keys().any(|&(noun_exp, verb_exp)| verb_exp > 0 && (noun > 0 || noun_exp == 0))
+    }
+
+    fn add(&self, other: &Poly) -> Option<Poly> {
+        let mut terms = self.0.clone();
+        for (&exps, &coeff) in &other.0 {
+            let sum = terms.get(&exps).cloned().unwrap_or(0).checked_add(coeff)?;
+            terms.insert(exps, sum);
+        }
+        Some(Poly(terms))
+    }
+
+    fn mul(&self, other: &Poly) -> Option<Poly> {
+        let mut ret = Poly(BTreeMap::new());
+        for (&(n1, v1), &c1) in &self.0 {
+            for (&(n2, v2), &c2) in &other.0 {
+                let mut term = BTreeMap::new();
+                term.insert((n1 + n2, v1 + v2), c1.checked_mul(c2)?);
+                ret = ret.add(&Poly(term))?;
+            }
+        }
+        Some(ret)
+    }
+
+    // None if the value overflows.
+    fn eval(&self, noun: u32, verb: u32) -> Option<u64> {
+        let mut total = 0u64;
+        for (&(noun_exp, verb_exp), &coeff) in &self.0 {
+            let term = (noun as u64).checked_pow(noun_exp)?
+                .checked_mul((verb as u64).checked_pow(verb_exp)?)?
+                .checked_mul(coeff)?;
+            total = total.checked_add(term)?;
+        }
+        Some(total)
+    }
+}
+
+impl fmt::Display for Poly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "0");
+        }
+        let mut terms = vec![];
+        for (&(noun_exp, verb_exp), &coeff) in self.0.iter().rev() {
+            let mut factors = vec![];
+            if coeff != 1 || (noun_exp == 0 && verb_exp == 0) {
+                factors.push(coeff.to_string());
+            }
+            for &(name, exp) in &[("noun", noun_exp), ("verb", verb_exp)] {
+                match exp {
+                    0 => (),
+                    1 => factors.push(name.to_string()),
+                    _ => factors.push(format!("{}^{}", name, exp)),
+                }
+            }
+            terms.push(factors.join("*"));
+        }
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+// Run the program with cells 1 and 2 holding the noun and verb as unknowns,
+// returning cell 0 as a polynomial in them.  A value read through an
+// address that depends on the noun or verb is not known; that is only an
+// error if it ends up somewhere that matters.
+fn interpret_symbolic(prog: &[u32]) -> Result<Poly, String> {
+    if prog.len() < 3 {
+        return Err("no room for the noun and verb".to_string());
+    }
+    let mut mem: Vec<Option<Poly>> = prog.iter().map(|&val| Some(Poly::constant(val))).collect();
+    mem[1] = Some(Poly::var(1, 0));
+    mem[2] = Some(Poly::var(0, 1));
+    // constant value of a cell, or why it has none
+    let constant = |mem: &[Option<Poly>], addr: usize| -> Result<u32, String> {
+        match mem.get(addr) {
+            None => Err(format!("address {} out of range", addr)),
+            Some(&None) => Err(format!("[{}] is not known", addr)),
+            Some(Some(poly)) => poly.as_constant()
+                .ok_or_else(|| format!("[{}] = {} depends on the noun or verb", addr, poly)),
+        }
+    };
+    let mut pos = 0;
+    loop {
+        let opcode = constant(&mem, pos)?;
+        if opcode == 99 {
+            break;
+        }
+        let operand = |mem: &[Option<Poly>], i: usize| -> Result<Option<Poly>, String> {
+            match mem.get(pos + i).cloned().unwrap_or(None).and_then(|addr| addr.as_constant()) {
+                Some(addr) => mem.get(addr as usize).cloned()
+                    .ok_or_else(|| format!("address {} out of range", addr)),
+                None => Ok(None),
+            }
         };
+        let op1 = operand(&mem, 1)?;
+        let op2 = operand(&mem, 2)?;
+        let ind_result = constant(&mem, pos + 3)? as usize;
+        let result = match (opcode, op1, op2) {
+            (1, Some(op1), Some(op2)) => Some(op1.add(&op2).ok_or("coefficient overflow")?),
+            (2, Some(op1), Some(op2)) => Some(op1.mul(&op2).ok_or("coefficient overflow")?),
+            (1, _, _) | (2, _, _) => None,
+            (other, _, _) => return Err(format!("invalid opcode {}", other)),
+        };
+        *mem.get_mut(ind_result)
+            .ok_or_else(|| format!("address {} out of range", ind_result))? = result;
         pos += 4;
     }
-    mem
+    mem[0].clone().ok_or_else(|| "[0] is not known".to_string())
 }
 
-fn read_prog(input: impl BufRead) -> Result<Vec<u32>, Box<dyn Error>> {
+// All (noun, verb) pairs below `limit` for which `poly` is `target`.  With
+// positive coefficients and the noun fixed, poly is strictly increasing in
+// the verb unless it no longer depends on it, so each noun needs a binary
+// search at most.
+fn solve(poly: &Poly, target: u32, limit: u32) -> Vec<(u32, u32)> {
+    let target = target as u64;
     let mut ret = vec![];
-    for line in input.lines() {
-        let line = line?;
-        for tok in line.split(",") {
-            ret.push(tok.trim().parse()?);
+    for noun in 0..limit {
+        if !poly.depends_on_verb(noun) {
+            if poly.eval(noun, 0) == Some(target) {
+                ret.extend((0..limit).map(|verb| (noun, verb)));
+            }
+            continue;
+        }
+        let (mut lo, mut hi) = (0, limit);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            // overflowing values are above any target
+            match poly.eval(noun, mid) {
+                Some(val) if val < target => lo = mid + 1,
+                _ => hi = mid,
+            }
+        }
+        if lo < limit && poly.eval(noun, lo) == Some(target) {
+            ret.push((noun, lo));
         }
     }
-    Ok(ret)
+    ret
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let prog = read_prog(io::stdin().lock())?;
-    'outer: for noun in 0..prog.len()-1 {
-        for verb in 0..prog.len()-1 {
-            let mut prog = prog.clone();
-            prog[1] = noun as u32;
-            prog[2] = verb as u32;
-            interpret(&mut prog);
-            if prog[0] == 19690720 {
-                println!("{}", 100 * noun + verb);
-                break 'outer;
+// Pairs that hit `target` in the real interpreter, which also rules out
+// pairs that overflow on the way.
+fn find_inputs(prog: &[u32], target: u32) -> Result<Vec<(u32, u32)>, String> {
+    let poly = interpret_symbolic(prog)?;
+    let limit = prog.len() as u32 - 1;
+    Ok(solve(&poly, target, limit).into_iter()
+       .filter(|&(noun, verb)| {
+           let mut mem = prog.to_vec();
+           mem[1] = noun;
+           mem[2] = verb;
+           interpret(&mut mem).is_ok() && mem[0] == target
+       })
+       .collect())
+}
+
+fn brute_force(prog: &[u32], target: u32) -> Vec<(u32, u32)> {
+    let mut ret = vec![];
+    for noun in 0..prog.len() as u32 - 1 {
+        for verb in 0..prog.len() as u32 - 1 {
+            let mut mem = prog.to_vec();
+            mem[1] = noun;
+            mem[2] = verb;
+            if interpret(&mut mem).is_ok() && mem[0] == target {
+                ret.push((noun, verb));
             }
         }
     }
+    ret
+}
+
+fn run_tests() -> Result<(), Box<dyn Error>> {
+    let mut a = Poly::var(1, 0).add(&Poly::constant(2)).unwrap();
+    a = a.mul(&a).unwrap().mul(&Poly::var(0, 1)).unwrap();
+    assert_eq!(a.to_string(), "noun^2*verb + 4*noun*verb + 4*verb");
+    assert_eq!(a.eval(3, 2), Some(50));
+    assert_eq!(solve(&a, 50, 10), vec![(3, 2)]);
+    assert_eq!(solve(&Poly::var(1, 0), 4, 3), vec![]);
+    assert_eq!(solve(&Poly::var(1, 0), 1, 3), vec![(1, 0), (1, 1), (1, 2)]);
+
+    // [0] = noun * verb + 5, after a read through the noun that is
+    // overwritten before it matters
+    let prog = [1,0,0,3,2,1,2,0,1,0,13,0,99,5];
+    assert_eq!(interpret_symbolic(&prog)?.to_string(), "noun*verb + 5");
+    assert_eq!(find_inputs(&prog, 11)?, brute_force(&prog, 11));
+    assert_eq!(find_inputs(&prog, 11)?, vec![(1, 6), (2, 3), (3, 2), (6, 1)]);
+    // with a zero noun, every verb gives 5
+    assert_eq!(find_inputs(&prog, 5)?, brute_force(&prog, 5));
+    assert_eq!(find_inputs(&prog, 5)?.len(), 25);
+    assert_eq!(solve(&Poly::var(1, 1), 0, 3), vec![(0, 0), (0, 1), (0, 2), (1, 0), (2, 0)]);
+    assert_eq!(interpret_symbolic(&[99, 0]).unwrap_err(), "no room for the noun and verb");
+    let err = interpret_symbolic(&[1,0,0,3,1,1,2,11,1,0,0,0,99]).unwrap_err();
+    assert_eq!(err, "[11] = noun + verb depends on the noun or verb".to_string());
+
+    let prog = read_prog(load::from_bytes(include_bytes!("2.input"))?)?;
+    for &target in &[19690720, 5434663, 1234567] {
+        assert_eq!(find_inputs(&prog, target)?, brute_force(&prog, target));
+    }
+    Ok(())
+}
+
+// The loaded program as the unsigned words this interpreter runs on.
+fn read_prog(prog: Vec<i64>) -> Result<Vec<u32>, Box<dyn Error>> {
+    let mut ret = vec![];
+    for val in prog {
+        if val < 0 || val > u32::MAX as i64 {
+            return Err(format!("{} is not a valid day 2 word", val).into());
+        }
+        ret.push(val as u32);
+    }
+    Ok(ret)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests()?;
+
+    let target = match std::env::args().nth(1) {
+        Some(arg) => arg.parse()?,
+        None => 19690720,
+    };
+    let prog = read_prog(load::from_stdin()?)?;
+    for (noun, verb) in find_inputs(&prog, target)? {
+        println!("{}", 100 * noun + verb);
+    }
 
     Ok(())
 }
@@ -1,172 +1,93 @@
-use std::io;
-use std::io::prelude::*;
-use std::error::Error;
-use std::sync::mpsc;
-use std::thread;
-
-fn interpret(mem: &mut [i32],
-             read_input: &mut dyn FnMut() -> i32,
-             write_output: &mut dyn FnMut(i32)) {
-    fn mem_read(mem: &[i32], pos: usize, is_immediate: bool) -> i32 {
-        let mut val = mem[pos];
-        if !is_immediate {
-            val = mem[val as usize];
-        }
-        val
-    }
-
-    fn decode_args_1(mem: &mut [i32], pos: usize) -> i32 {
-        let opcode = mem[pos] / 100;
-        let immediate_1 = opcode % 10 == 1;
-        mem_read(mem, pos + 1, immediate_1)
-    }
+mod intcode;
 
-    fn decode_args_2(mem: &mut [i32], pos: usize) -> (i32, i32) {
-        let mut opcode = mem[pos] / 100;
-        let immediate_1 = opcode % 10 == 1;
-        opcode /= 10;
-        let immediate_2 = opcode % 10 == 1;
-        (mem_read(mem, pos + 1, immediate_1),
-         mem_read(mem, pos + 2, immediate_2))
-    }
-
-    let mut pos = 0;
+use std::error::Error;
+use intcode::{Machine, Memory};
+use intcode::network::{Network, NetworkError};
+use intcode::scheduler::{Report, Scheduler};
+use intcode::load;
 
-    while mem[pos] != 99 {
-        //println!("decoding {} at position {}", mem[pos], pos);
-        match mem[pos] % 100 {
-            1 => {
-                let (op1, op2) = decode_args_2(mem, pos);
-                mem[mem[pos + 3] as usize] = op1.checked_add(op2)
-                    .expect(&format!("overflow {}+{}", op1, op2));
-                pos += 4;
-            }
-            2 => {
-                let (op1, op2) = decode_args_2(mem, pos);
-                mem[mem[pos + 3] as usize] = op1.checked_mul(op2)
-                    .expect(&format!("overflow {}*{}", op1, op2));
-                pos += 4;
-            }
-            3 => {
-                mem[mem[pos + 1] as usize] = read_input();
-                pos += 2;
-            }
-            4 => {
-                let val = decode_args_1(mem, pos);
-                write_output(val);
-                pos += 2;
-            }
-            5 => {
-                let (op1, op2) = decode_args_2(mem, pos);
-                if op1 != 0 {
-                    pos = op2 as usize;
-                }
-                else {
-                    pos += 3;
-                }
-            }
-            6 => {
-                let (op1, op2) = decode_args_2(mem, pos);
-                if op1 == 0 {
-                    pos = op2 as usize;
-                }
-                else {
-                    pos += 3;
-                }
-            }
-            7 => {
-                let (op1, op2) = decode_args_2(mem, pos);
-                mem[mem[pos + 3] as usize] = if op1 < op2 {1} else {0};
-                pos += 4;
-            }
-            8 => {
-                let (op1, op2) = decode_args_2(mem, pos);
-                mem[mem[pos + 3] as usize] = if op1 == op2 {1} else {0};
-                pos += 4;
-            }
-            other => panic!("invalid instruction {}", other),
-        };
-    }
+fn amplify(program: &[i64], phase_settings: [i64; 5]) -> i64 {
+    let mut net = Network::new();
+    let amps: Vec<usize> = phase_settings.iter()
+        .map(|&phase| net.add(Machine::new(program), &[phase]))
+        .collect();
+    net.ring(&amps);
+    net.push_input(amps[0], 0);
+    net.tap(amps[4]);
+    *net.run().unwrap().last().unwrap()
 }
 
-fn amplify(program: &[i32], phase_settings: [i32; 5]) -> i32 {
-    let (first_tx, mut cur_rx) = mpsc::channel();
-    let mut threads = vec![];
+fn run_tests() -> Result<(), Box<dyn Error>> {
+    assert_eq!(amplify(&load::parse("3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5")?,
+                       [9,8,7,6,5]),
+               139629729);
+    assert_eq!(amplify(&load::parse("3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10")?,
+                       [9,7,8,5,6]),
+               18216);
 
-    for (i, &phase) in phase_settings.iter().enumerate() {
-        let mut running = program.to_vec();
-        let (tx, rx) = mpsc::channel();
-        let mut get_input = {
-            let mut first = true;
-            move || {
-                if first {
-                    first = false;
-                    phase
-                } else {
-                    cur_rx.recv().expect("failed to receive input")
-                }
-            }
-        };
-        let mut set_output = move |val| {
-            tx.send(val).expect("failed to send output");
-        };
-        threads.push(
-            thread::Builder::new()
-                .name(format!("amp{}", i)).spawn(
-                    move || interpret(&mut running,
-                                      &mut get_input, &mut set_output)
-                ).unwrap());
-        cur_rx = rx;
-    }
+    // one node doubling its input, broadcast to two nodes adding 1 and 2
+    let mut net = Network::new();
+    let double = net.add(Machine::new(&[3,9,1002,9,2,9,4,9,99,0]), &[5]);
+    let add1 = net.add(Machine::new(&[3,9,1001,9,1,9,4,9,99,0]), &[]);
+    let add2 = net.add(Machine::new(&[3,9,1001,9,2,9,4,9,99,0]), &[]);
+    net.broadcast(double, &[add1, add2]);
+    net.tap(add1);
+    net.tap(add2);
+    assert_eq!(net.run(), Ok(vec![11, 12]));
 
-    let (thrust_tx, thrust_rx) = mpsc::channel();
-    threads.push(thread::spawn(move || {
-        first_tx.send(0).expect("failed to send seed value");
-        while let Some(val) = cur_rx.recv().ok() {
-            thrust_tx.send(val).expect("failed to send output");
-            if let Err(_) = first_tx.send(val) {
-                break
-            }
-        }
-    }));
-    for t in threads {
-        t.join().expect("spread the panic");
-    }
-    let mut thrust = 0;
-    while let Some(val) = thrust_rx.try_recv().ok() {
-        thrust = val;
-    }
-    thrust
-}
+    // a ring where each node waits for the other first
+    let mut net = Network::new();
+    let a = net.add(Machine::new(&[3,9,1001,9,1,9,4,9,99,0]), &[]);
+    let b = net.add(Machine::new(&[3,9,1001,9,1,9,4,9,99,0]), &[]);
+    net.ring(&[a, b]);
+    assert_eq!(net.run(), Err(NetworkError::Deadlock(vec![a, b])));
 
-fn read_prog(input: impl BufRead) -> Result<Vec<i32>, Box<dyn Error>> {
-    let mut ret = vec![];
-    for line in input.lines() {
-        ret.extend(read_prog_from(&line?)?);
+    // errors name the node
+    let mut net = Network::new();
+    net.add(Machine::new(&[99]), &[]);
+    let bad = net.add(Machine::new(&[42]), &[]);
+    match net.run() {
+        Err(NetworkError::Fault(node, err)) => assert_eq!((node, err.instruction), (bad, 42)),
+        other => panic!("unexpected result {:?}", other),
     }
-    Ok(ret)
-}
 
-fn read_prog_from(input: &str) -> Result<Vec<i32>, Box<dyn Error>> {
-    let mut ret = vec![];
-    for tok in input.split(",") {
-        ret.push(tok.trim().parse()?);
-    }
-    Ok(ret)
-}
+    // the scheduler reports a machine that halted and one left waiting
+    let mut sched = Scheduler::new();
+    let writer = sched.add(Machine::new(&[104,7,99]));
+    let reader = sched.add(Machine::new(&[3,0,3,0,99]));
+    let mut outputs = vec![];
+    let report = sched.run(&mut |machines, id, val| {
+        outputs.push((id, val));
+        machines[reader].push_input(val);
+    }).unwrap();
+    assert_eq!(outputs, vec![(writer, 7)]);
+    assert_eq!(report, Report { halted: vec![writer], stuck: vec![reader] });
+    assert!(report.is_deadlock());
 
-fn run_tests() -> Result<(), Box<dyn Error>> {
-    assert_eq!(amplify(&read_prog_from("3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5")?,
-                       [9,8,7,6,5]),
-               139629729);
-    assert_eq!(amplify(&read_prog_from("3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10")?,
-                       [9,7,8,5,6]),
-               18216);
+    // the middle machine echoes its input from the same position each time,
+    // and the one it feeds still gets to read it
+    let mut sched = Scheduler::new();
+    let last = sched.add(Machine::new(&[3,0,99]));
+    let echo = sched.add(Machine::new(&[1105,1,6,0,4,100,3,100,1105,1,4]));
+    let first = sched.add(Machine::new(&[104,5,99]));
+    let report = sched.run(&mut |machines, id, val| {
+        let dest = if id == first { echo } else { last };
+        machines[dest].push_input(val);
+    }).unwrap();
+    assert_eq!(report, Report { halted: vec![last, first], stuck: vec![echo] });
+    assert_eq!(sched.machines[last].mem.read(0), 5);
 
-        Ok(())
+    let mut net = Network::new();
+    let last = net.add(Machine::new(&[3,0,99]), &[]);
+    let echo = net.add(Machine::new(&[1105,1,6,0,4,100,3,100,1105,1,4]), &[]);
+    let first = net.add(Machine::new(&[104,5,99]), &[]);
+    net.chain(&[first, echo, last]);
+    assert_eq!(net.run(), Err(NetworkError::Deadlock(vec![echo])));
+
+    Ok(())
 }
 
-fn find_max_thrust(prog: &[i32]) -> i32 {
+fn find_max_thrust(prog: &[i64]) -> i64 {
     let mut maxval = -1;
     let mut winner = None;
     for i in 5..10 {
@@ -203,7 +124,7 @@ fn find_max_thrust(prog: &[i32]) -> i32 {
 
 fn main() -> Result<(), Box<dyn Error>> {
     run_tests()?;
-    let prog = read_prog(io::stdin().lock())?;
+    let prog = load::from_stdin()?;
     println!("{}", find_max_thrust(&prog));
     Ok(())
 }
@@ -1,174 +1,256 @@
-#[derive(PartialEq, Debug)]
-enum AddrMode {
-    POSITION,
-    IMMEDIATE,
-    RELATIVE
-}
-
-fn interpret(mem: &mut Vec<i64>,
-             read_input: &mut dyn FnMut() -> i64,
-             write_output: &mut dyn FnMut(i64)) {
-    fn read_direct(mem: &[i64], pos: usize) -> i64 {
-        if pos >= mem.len() {
-            0
-        }
-        else {
-            mem[pos]
-        }
-    }
-    fn mem_follow_mode(val: i64, mem: &[i64], relbase: usize, mode: AddrMode)
-                    -> i64 {
-        match mode {
-            AddrMode::POSITION => read_direct(mem, val as usize),
-            AddrMode::IMMEDIATE => val,
-            AddrMode::RELATIVE => read_direct(mem, (relbase as i64 + val) as usize),
-        }
-    }
-    fn mem_read(mem: &[i64], pos: usize, relbase: usize, mode: AddrMode) -> i64 {
-        mem_follow_mode(read_direct(mem, pos), mem, relbase, mode)
-    }
-
-    fn decode_mode(digit: i64) -> AddrMode {
-        match digit {
-            0 => AddrMode::POSITION,
-            1 => AddrMode::IMMEDIATE,
-            2 => AddrMode::RELATIVE,
-            other => panic!("invalid opcode {}", other),
-        }
-    }
+mod intcode;
 
-    fn decode_next(mem: &mut [i64], pos: usize, relbase: usize, modes: i64) -> (i64, i64) {
-        (mem_read(mem, pos, relbase, decode_mode(modes % 10)),
-         modes / 10)
-    }
-    fn decode_operands_1(mem: &mut [i64], pos: usize, relbase: usize) -> (i64, i64) {
-        let modes = mem[pos] / 100;
-        decode_next(mem, pos + 1, relbase, modes)
-    }
-    fn decode_operands_2(mem: &mut [i64], pos: usize, relbase: usize) -> (i64, i64, i64) {
-        let modes = mem[pos] / 100;
-        let (val1, modes) = decode_next(mem, pos + 1, relbase, modes);
-        let (val2, modes) = decode_next(mem, pos + 2, relbase, modes);
-        (val1, val2, modes)
-    }
-
-    fn mem_write(mem: &mut Vec<i64>, relbase: usize,
-                 raw_addr: i64, mode: AddrMode, val: i64) {
-        assert_ne!(mode, AddrMode::IMMEDIATE);
-        let pos = if let AddrMode::RELATIVE = mode {
-            (raw_addr as isize + relbase as isize) as usize
-        }
-        else {raw_addr as usize};
-        if pos >= mem.len() {
-            mem.extend(std::iter::repeat(0).take(pos - mem.len() + 1));
-        }
-        mem[pos] = val;
-    }
+use std::time::Duration;
+use intcode::{interpret, interpret_limited, interpret_words, ArithOp, ErrorKind, Event, Limits,
+              Machine, Memory, PagedMemory, Word, DENSE_MAX_ADDR};
+use intcode::bigint::BigInt;
+use intcode::device::{self, Ascii, FrameHandler, Framer, Queue, Tee, Text};
+use intcode::word;
+use intcode::load;
+use intcode::replay::Io;
+use intcode::snapshot;
+use intcode::threaded::{self, Message};
 
-    let mut pos = 0usize;
-    let mut relbase = 0usize;
-
-    while mem[pos] != 99 {
-        match mem[pos] % 100 {
-            1 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = op1.checked_add(op2)
-                    .expect(&format!("overflow {}+{}", op1, op2));
-                mem_write(mem, relbase, mem[pos + 3], decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            2 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = op1.checked_mul(op2)
-                    .expect(&format!("overflow {}*{}", op1, op2));
-                mem_write(mem, relbase, mem[pos + 3], decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            3 => {
-                let write_mode = decode_mode(mem[pos] / 100);
-                let val = read_input();
-                mem_write(mem, relbase, mem[pos + 1], write_mode, val);
-                pos += 2;
-            }
-            4 => {
-                let (val, modes) = decode_operands_1(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                write_output(val);
-                pos += 2;
-            }
-            5 => {
-                let (op1, op2, modes) = decode_operands_2(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                if op1 != 0 {
-                    pos = op2 as usize;
-                }
-                else {
-                    pos += 3;
-                }
-            }
-            6 => {
-                let (op1, op2, modes) = decode_operands_2(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                if op1 == 0 {
-                    pos = op2 as usize;
-                }
-                else {
-                    pos += 3;
-                }
-            }
-            7 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = if op1 < op2 {1} else {0};
-                mem_write(mem, relbase, mem[pos + 3],
-                          decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            8 => {
-                let (op1, op2, write_mode_raw) = decode_operands_2(mem, pos, relbase);
-                let val = if op1 == op2 {1} else {0};
-                mem_write(mem, relbase, mem[pos + 3],
-                          decode_mode(write_mode_raw), val);
-                pos += 4;
-            }
-            9 => {
-                let (val, modes) = decode_operands_1(mem, pos, relbase);
-                assert_eq!(modes, 0);
-                relbase = (relbase as isize + val as isize) as usize;
-                pos += 2;
-            }
-            other => panic!("invalid instruction {}", other),
-        };
-    }
+fn interpret_output(prog: &[i64], input: i64) -> Vec<i64> {
+    Machine::new(prog).outputs(vec![input]).collect::<Result<_, _>>().unwrap()
 }
 
+fn words<W: Word>(prog: &[i64]) -> Vec<W> {
+    word::convert(prog).unwrap()
+}
 
-fn interpret_output(mem: &mut Vec<i64>, input: i64) -> Vec<i64> {
+fn outputs<W: Word>(mut mem: Vec<W>, input: W) -> Result<Vec<W>, ErrorKind<W>> {
     let mut outvec = vec![];
-    interpret(mem, &mut || input, &mut |val| outvec.push(val));
-    outvec
+    interpret_words(&mut mem, &mut || input.clone(), &mut |val| outvec.push(val))
+        .map_err(|err| err.kind)?;
+    Ok(outvec)
 }
 
 fn run_tests() {
-    assert_eq!(interpret_output(&mut vec![109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99], 0),
+    assert_eq!(interpret_output(&vec![109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99], 0),
                vec![109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99]);
-    assert!(interpret_output(&mut vec![1102,34915192,34915192,7,4,7,99,0], 0)[0]
+    assert!(interpret_output(&[1102,34915192,34915192,7,4,7,99,0], 0)[0]
             >= 1_000_000_000_000_000);
-    assert_eq!(interpret_output(&mut vec![104,1125899906842624,99], 0)[0],
+    assert_eq!(interpret_output(&[104,1125899906842624,99], 0)[0],
                1125899906842624);
-}
 
-fn read_prog(input: &[u8]) -> Vec<i64> {
-    let input = std::str::from_utf8(input).unwrap();
-    let mut ret = vec![];
-    for tok in input.split(",") {
-        ret.push(tok.trim().parse().unwrap());
+    let err = interpret(&mut vec![1101,1,1,0,42], &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!((err.pc, err.instruction, err.kind), (4, 42, ErrorKind::InvalidOpcode(42)));
+    let err = interpret(&mut vec![301,0,0,0,99], &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidMode(3));
+    let err = interpret(&mut vec![11101,1,1,0,99], &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ImmediateWrite);
+    let err = interpret(&mut vec![1,-1,0,0,99], &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NegativeAddress(-1));
+    let err = interpret(&mut vec![1102,4611686018427387904,2,0,99], &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Overflow { op: ArithOp::Mul, lhs: 4611686018427387904, rhs: 2 });
+    let err = interpret(&mut vec![1101,1,1,1 << 40,99], &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::AddressTooLarge(1 << 40));
+    assert!(interpret(&mut vec![1101,1,1,DENSE_MAX_ADDR as i64,99], &mut || 0, &mut |_| ()).is_ok());
+    // a relative address overflows as an addition, even in a mul
+    let err = interpret(&mut vec![109,i64::MAX,1202,1,2,3,99], &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Overflow { op: ArithOp::Add, lhs: i64::MAX, rhs: 1 });
+    assert_eq!(err.to_string(), format!("at 2 (instruction 1202): overflow {}+1", i64::MAX));
+    // an input with nowhere to go stays queued
+    let mut machine = Machine::new(&[103,0,99]);
+    machine.push_input(5);
+    assert_eq!(machine.run().unwrap_err().kind, ErrorKind::ImmediateWrite);
+    assert_eq!(machine.input, vec![5]);
+    let mut machine = Machine::new(&[203,-1,99]);
+    machine.push_input(5);
+    assert_eq!(machine.run().unwrap_err().kind, ErrorKind::NegativeAddress(-1));
+    assert_eq!(machine.input, vec![5]);
+
+    // far writes only allocate the pages they touch
+    let mut machine = Machine::new(&[1101,6,7,1 << 40,4,1 << 40,99]);
+    machine.mem.max_addr = 1 << 41;
+    assert_eq!(machine.run(), Ok(Event::Output(13)));
+    assert_eq!(machine.mem.page_count(), 1);
+    assert_eq!(machine.mem.read((1 << 40) + 1), 0);
+    let mut machine = Machine::with_memory(PagedMemory::<i64>::with_max_addr(vec![109,100,21101,1,2,0,99], 100));
+    assert_eq!(machine.run(), Ok(Event::Halted));
+    assert_eq!(machine.mem.read(100), 3);
+    assert_eq!(machine.mem.segments().len(), 2);
+    machine.pos = 0;
+    machine.relbase = 1;
+    assert_eq!(machine.run().unwrap_err().kind, ErrorKind::AddressTooLarge(101));
+
+    // limits stop an endless loop counting in [9], and the machine can go on
+    let counter = [1001,9,1,9,1105,1,0,99,0,0];
+    let mut machine = Machine::new(&counter);
+    let limits = Limits { instructions: Some(10), time: None };
+    let err = machine.run_limited(limits, &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!((err.pc, err.kind), (0, ErrorKind::BudgetExhausted(10)));
+    assert_eq!(machine.mem.read(9), 5);
+    let limits = Limits { instructions: Some(1), time: None };
+    let err = machine.run_limited(limits, &mut || 0, &mut |_| ()).unwrap_err();
+    assert_eq!((err.pc, err.kind), (4, ErrorKind::BudgetExhausted(1)));
+    assert_eq!(machine.mem.read(9), 6);
+    let limits = Limits { instructions: None, time: Some(Duration::from_millis(10)) };
+    let err = interpret_limited(&mut counter.to_vec(), limits, &mut || 0, &mut |_| ()).unwrap_err();
+    match err.kind {
+        ErrorKind::BudgetExhausted(executed) => assert!(executed > 0),
+        other => panic!("unexpected error {:?}", other),
+    }
+    // the final hlt does not count
+    let mut outvec = vec![];
+    let limits = Limits { instructions: Some(2), time: None };
+    assert!(interpret_limited(&mut vec![3,0,4,0,99], limits, &mut || 7, &mut |val| outvec.push(val)).is_ok());
+    assert_eq!(outvec, vec![7]);
+
+    // other word sizes, where overflow depends on the word
+    assert_eq!(outputs::<i32>(vec![1102,65536,65536,0,99], 0),
+               Err(ErrorKind::Overflow { op: ArithOp::Mul, lhs: 65536, rhs: 65536 }));
+    assert_eq!(outputs::<i32>(vec![1101,1,1,1 << 24,99], 0),
+               Err(ErrorKind::AddressTooLarge(1 << 24)));
+    assert_eq!(word::convert::<i32>(&[1, 1 << 40]), Err(word::Overflow { index: 1, val: 1 << 40 }));
+    assert_eq!(word::convert::<i32>(&[1, 1 << 40]).unwrap_err().to_string(),
+               "overflow loading word 1: 1099511627776 does not fit");
+    assert_eq!(i32::from_i64(-1 << 31), Some(i32::MIN));
+    let doubled = [1102,4611686018427387904,2,0,4,0,99];
+    assert_eq!(outputs(words::<i64>(&doubled), 0),
+               Err(ErrorKind::Overflow { op: ArithOp::Mul, lhs: 4611686018427387904, rhs: 2 }));
+    assert_eq!(outputs(words::<i128>(&doubled), 0), Ok(vec![1 << 63]));
+    let square: Vec<BigInt> = "2,7,7,8,4,8,99,1267650600228229401496703205376,0".split(',')
+        .map(|tok| tok.parse().unwrap())
+        .collect();
+    assert_eq!(outputs(square, BigInt::from(0)).unwrap()[0].to_string(),
+               "1606938044258990275541962092341162602522202993782792835301376");
+    let far: BigInt = "-100000000000000000000".parse().unwrap();
+    assert_eq!(outputs(vec![BigInt::from(4), far.clone(), BigInt::from(99)], BigInt::from(0)),
+               Err(ErrorKind::NegativeAddress(far)));
+    assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+    assert_eq!(BigInt::from(-5).checked_add(&BigInt::from(3)), Some(BigInt::from(-2)));
+    assert!(BigInt::from(-5) < BigInt::from(3));
+    assert!("12a".parse::<BigInt>().is_err());
+
+    // outputs are produced lazily, and running out of input is an error
+    let repeat: Vec<i64> = Machine::new(&[104,1,1105,1,0]).outputs(vec![]).take(3)
+        .collect::<Result<_, _>>().unwrap();
+    assert_eq!(repeat, vec![1, 1, 1]);
+    let mut echo = Machine::new(&[3,0,4,0,3,0,4,0,99]).outputs(vec![5]);
+    assert_eq!(echo.next(), Some(Ok(5)));
+    let err = echo.next().unwrap().unwrap_err();
+    assert_eq!((err.pc, err.kind), (4, ErrorKind::InputExhausted));
+    assert_eq!(echo.next(), None);
+    let mut bad = Machine::new(&[104,1,42]).outputs(vec![]);
+    assert_eq!(bad.next(), Some(Ok(1)));
+    assert_eq!(bad.next().unwrap().unwrap_err().kind, ErrorKind::InvalidOpcode(42));
+    assert_eq!(bad.next(), None);
+
+    // snapshots keep the whole machine, pages past the image included
+    let mut machine = Machine::new(&[3,9,1001,9,5,9,4,9,99,0]);
+    machine.push_input(7);
+    machine.step().unwrap();
+    machine.push_input(3);
+    let text = snapshot::to_string(&machine);
+    assert_eq!(text, concat!("intcode snapshot 1\n",
+                             "pos 2\n",
+                             "relbase 0\n",
+                             "input 3\n",
+                             "mem 3,9,1001,9,5,9,4,9,99,7\n"));
+    assert_eq!(snapshot::from_str(&text).unwrap(), machine);
+    assert_eq!(snapshot::from_str("intcode snapshot 1\npos 2\nrelbase x\ninput\nmem 99\n").unwrap_err().line, 3);
+    let mut machine = Machine::new(&[1101,5,0,1030,99]);
+    machine.run().unwrap();
+    let text = snapshot::to_string(&machine);
+    assert!(text.contains("mem 1101,5,0,1030,99\npage 1024 0,0,0,0,0,0,5,0,"));
+    assert_eq!(snapshot::from_str(&text).unwrap(), machine);
+    assert_eq!(snapshot::from_str("intcode snapshot 1\npos 0\nrelbase 0\ninput\nmem 99\npage x 1\n").unwrap_err().line, 6);
+
+    // threaded machines: a real output of 99 is not the end of the program
+    let vm = threaded::spawn(Machine::new(&[104,99,99]));
+    assert_eq!(vm.recv(), Some(Message::Output(99)));
+    assert_eq!(vm.recv(), Some(Message::Halted));
+    assert_eq!(vm.recv(), None);
+    // cancelled while waiting for input, and while busy in a loop
+    let vm = threaded::spawn(Machine::new(&[3,0,99]));
+    vm.cancel();
+    assert_eq!(vm.recv(), Some(Message::Cancelled));
+    assert_eq!(vm.join().pos, 0);
+    let vm = threaded::spawn(Machine::new(&[1105,1,0]));
+    vm.cancel();
+    assert_eq!(vm.recv(), Some(Message::Cancelled));
+    match threaded::spawn(Machine::new(&[42])).recv() {
+        Some(Message::Error(err)) => assert_eq!(err.kind, ErrorKind::InvalidOpcode(42)),
+        other => panic!("unexpected message {:?}", other),
+    }
+    // an echo stopped after one round, then joined
+    let echo = threaded::spawn(Machine::new(&[3,100,4,100,1105,1,0]));
+    echo.send(7);
+    assert_eq!(echo.recv(), Some(Message::Output(7)));
+    echo.cancel();
+    assert_eq!(echo.recv(), Some(Message::Cancelled));
+    assert_eq!(echo.join().mem.read(100), 7);
+
+    // devices: echo, in, out, loop
+    let echo = [3, 100, 4, 100, 1105, 1, 0];
+    let mut queue = Queue::new(&[7, 8]);
+    let err = device::run(&mut Machine::new(&echo), &mut queue).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InputExhausted);
+    assert_eq!(queue.output, vec![7, 8]);
+    let mut log = vec![];
+    {
+        let mut tee = Tee::new(Queue::new(&[5]), |io| log.push(io));
+        let _ = device::run(&mut Machine::new(&echo), &mut tee);
+        assert_eq!(tee.inner.output, vec![5]);
+    }
+    assert_eq!(log, vec![Io::In(5), Io::Out(5)]);
+    // on a machine with wider words
+    let mut queue = Queue::new(&[1i128 << 62]);
+    device::run(&mut Machine::with_memory(words::<i128>(&[3,9,1002,9,2,9,4,9,99,0])), &mut queue)
+        .unwrap();
+    assert_eq!(queue.output, vec![1 << 63]);
+    // prints "hi", echoes one line, prints 1000 and a partial "ok"
+    let greet = [104, 104, 104, 105, 104, 10,
+                 3, 100, 4, 100, 1008, 100, 10, 101, 1006, 101, 6,
+                 104, 1000, 104, 111, 104, 107, 99];
+    let mut lines = vec!["abc".to_string()].into_iter();
+    let mut texts = vec![];
+    device::run(&mut Machine::new(&greet),
+                &mut Ascii::new(|| lines.next(), |text| texts.push(text))).unwrap();
+    assert_eq!(texts, vec![Text::Line("hi".to_string()),
+                           Text::Line("abc".to_string()),
+                           Text::Value(1000),
+                           Text::Partial("ok".to_string())]);
+    // frames of 2
+    struct Pairs(Vec<(i64, i64)>);
+    impl FrameHandler for Pairs {
+        fn read(&mut self) -> Option<i64> { None }
+        fn frame(&mut self, frame: &[i64]) { self.0.push((frame[0], frame[1])); }
     }
-    ret
+    let mut pairs = Framer::new(2, Pairs(vec![]));
+    device::run(&mut Machine::new(&[104, 1, 104, 2, 104, 3, 99]), &mut pairs).unwrap();
+    assert_eq!(pairs.handler.0, vec![(1, 2)]);
+    assert_eq!(pairs.pending(), &[3]);
+
+    // the loader takes what editors leave behind
+    assert_eq!(load::parse("# quine\r\n109,1,204,-1,\r\n\r\n1001 , 100,# add\n99,\n").unwrap(),
+               vec![109, 1, 204, -1, 1001, 100, 99]);
+    assert_eq!(load::from_bytes(b"").unwrap(), vec![]);
+    for &(text, index, line, column, token) in &[("1,2,x3", 2, 1, 5, "x3"),
+                                                  ("1,\n\n 2, 3 4\n", 2, 3, 5, "3 4"),
+                                                  ("1,,-", 1, 1, 4, "-"),
+                                                  ("99999999999999999999", 0, 1, 1, "99999999999999999999")] {
+        match load::parse(text) {
+            Err(load::LoadError::BadNumber { index: i, line: l, column: c, token: ref t })
+                if (i, l, c, t.as_str()) == (index, line, column, token) => (),
+            other => panic!("unexpected result {:?} for {:?}", other, text),
+        }
+    }
+    let err = load::parse("1,2\n3,x").unwrap_err();
+    assert_eq!(err.to_string(), "line 2, column 3: number 3 is invalid: \"x\"");
+
+    let boost = load::from_bytes(include_bytes!("9.input")).unwrap();
+    assert_eq!(outputs(words::<i128>(&boost), 1), Ok(vec![3460311188]));
+    assert_eq!(outputs(words::<i128>(&boost), 2), Ok(vec![42202]));
+    assert_eq!(outputs(words::<BigInt>(&boost), BigInt::from(1)),
+               Ok(vec![BigInt::from(3460311188i64)]));
 }
 
 fn main() {
     run_tests();
 
-    let mut prog = read_prog(include_bytes!("9.input"));
-    println!("{:?}", interpret_output(&mut prog, 2));
+    let prog = load::from_bytes(include_bytes!("9.input")).unwrap();
+    println!("{:?}", interpret_output(&prog, 2));
 }
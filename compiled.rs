@@ -0,0 +1,42 @@
+// Intcode programs compiled to Rust by intcode_compile.  SOURCES lists the
+// program and variable words each module was generated from, and
+// intcode_compile's tests fail while a module is out of date.  Regenerate
+// one with, for example,
+//
+//   intcode_compile --var 1 --var 2 2.input > compiled/day2.rs
+
+#![allow(dead_code)]
+
+pub mod bad_mode;
+pub mod bad_opcode;
+pub mod boost;
+pub mod day2;
+pub mod far_write;
+pub mod immediate_write;
+pub mod large_mul;
+pub mod large_out;
+pub mod negative_address;
+pub mod overflow;
+pub mod quine;
+pub mod relative_address;
+pub mod relative_base;
+
+// (module, program, variable words, generated source)
+pub const SOURCES: &[(&str, &str, &[usize], &str)] = &[
+    ("bad_mode", "301,0,0,0,99", &[], include_str!("compiled/bad_mode.rs")),
+    ("bad_opcode", "1101,1,1,0,42", &[], include_str!("compiled/bad_opcode.rs")),
+    ("boost", include_str!("9.input"), &[], include_str!("compiled/boost.rs")),
+    ("day2", include_str!("2.input"), &[1, 2], include_str!("compiled/day2.rs")),
+    ("far_write", "1101,1,1,1099511627776,99", &[], include_str!("compiled/far_write.rs")),
+    ("immediate_write", "11101,1,1,0,99", &[], include_str!("compiled/immediate_write.rs")),
+    ("large_mul", "1102,34915192,34915192,7,4,7,99,0", &[], include_str!("compiled/large_mul.rs")),
+    ("large_out", "104,1125899906842624,99", &[], include_str!("compiled/large_out.rs")),
+    ("negative_address", "1,-1,0,0,99", &[], include_str!("compiled/negative_address.rs")),
+    ("overflow", "1102,4611686018427387904,2,0,99", &[], include_str!("compiled/overflow.rs")),
+    ("quine", "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99", &[],
+     include_str!("compiled/quine.rs")),
+    ("relative_address", "109,9223372036854775807,204,1,99", &[],
+     include_str!("compiled/relative_address.rs")),
+    ("relative_base", "109,9223372036854775807,109,1,99", &[],
+     include_str!("compiled/relative_base.rs")),
+];
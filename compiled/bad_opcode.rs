@@ -0,0 +1,74 @@
+// Generated by intcode::compile; do not edit.
+
+#![allow(unused_mut, unused_variables, unused_macros, dead_code, clippy::all)]
+
+use crate::intcode::{IntcodeError, Machine, Memory, DENSE_MAX_ADDR};
+
+// Leave the compiled code, so the interpreter runs the current instruction.
+macro_rules! get {
+    ($e:expr) => {
+        match $e {
+            Some(val) => val,
+            None => break,
+        }
+    };
+}
+
+fn load(mem: &Vec<i64>, addr: i64) -> Option<i64> {
+    if addr < 0 { None } else { Some(mem.read(addr as usize)) }
+}
+
+fn dest(addr: i64) -> Option<usize> {
+    if addr < 0 || addr as u64 > DENSE_MAX_ADDR as u64 { None } else { Some(addr as usize) }
+}
+
+fn store(mem: &mut Vec<i64>, addr: i64, val: i64, modified: &mut bool) -> Option<()> {
+    let addr = dest(addr)?;
+    mem.write(addr, val).ok()?;
+    if addr < IMAGE.len() && CODE[addr] != 0 && val != IMAGE[addr] {
+        *modified = true;
+    }
+    Some(())
+}
+
+fn jump(target: i64) -> Option<usize> {
+    if target < 0 { None } else { Some(target as usize) }
+}
+
+const IMAGE: [i64; 5] = [
+    1101, 1, 1, 0, 42,
+];
+
+const CODE: [u8; 5] = [
+    1, 1, 1, 1, 0,
+];
+
+pub fn interpret(mem: &mut Vec<i64>,
+                 read_input: &mut dyn FnMut() -> i64,
+                 write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let mut pc = 0usize;
+    let mut rb = 0i64;
+    let mut modified = (0..IMAGE.len())
+        .any(|addr| CODE[addr] != 0 && mem.read(addr) != IMAGE[addr]);
+    loop {
+        match pc {
+            // add #1, #1, [0]
+            0 => {
+                if modified && (mem.read(0) != 1101 || mem.read(1) != 1 || mem.read(2) != 1 || mem.read(3) != 0) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 0, val, &mut modified));
+                pc = 4;
+            }
+_ => break,
+        }
+    }
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    machine.pos = pc;
+    machine.relbase = rb;
+    let result = machine.run_with(read_input, write_output);
+    *mem = machine.mem;
+    result
+}
@@ -0,0 +1,2918 @@
+// Generated by intcode::compile; do not edit.
+
+#![allow(unused_mut, unused_variables, unused_macros, dead_code, clippy::all)]
+
+use crate::intcode::{IntcodeError, Machine, Memory, DENSE_MAX_ADDR};
+
+// Leave the compiled code, so the interpreter runs the current instruction.
+macro_rules! get {
+    ($e:expr) => {
+        match $e {
+            Some(val) => val,
+            None => break,
+        }
+    };
+}
+
+fn load(mem: &Vec<i64>, addr: i64) -> Option<i64> {
+    if addr < 0 { None } else { Some(mem.read(addr as usize)) }
+}
+
+fn dest(addr: i64) -> Option<usize> {
+    if addr < 0 || addr as u64 > DENSE_MAX_ADDR as u64 { None } else { Some(addr as usize) }
+}
+
+fn store(mem: &mut Vec<i64>, addr: i64, val: i64, modified: &mut bool) -> Option<()> {
+    let addr = dest(addr)?;
+    mem.write(addr, val).ok()?;
+    if addr < IMAGE.len() && CODE[addr] != 0 && val != IMAGE[addr] {
+        *modified = true;
+    }
+    Some(())
+}
+
+fn jump(target: i64) -> Option<usize> {
+    if target < 0 { None } else { Some(target as usize) }
+}
+
+const IMAGE: [i64; 973] = [
+    1102, 34463338, 34463338, 63, 1007, 63, 34463338, 63, 1005, 63, 53, 1102, 3, 1, 1000, 109,
+    988, 209, 12, 9, 1000, 209, 6, 209, 3, 203, 0, 1008, 1000, 1, 63, 1005,
+    63, 65, 1008, 1000, 2, 63, 1005, 63, 904, 1008, 1000, 0, 63, 1005, 63, 58,
+    4, 25, 104, 0, 99, 4, 0, 104, 0, 99, 4, 17, 104, 0, 99, 0,
+    0, 1101, 37, 0, 1012, 1101, 26, 0, 1008, 1101, 0, 39, 1016, 1101, 0, 36,
+    1007, 1101, 669, 0, 1024, 1102, 1, 29, 1009, 1102, 0, 1, 1020, 1102, 24, 1,
+    1003, 1102, 22, 1, 1013, 1101, 0, 30, 1019, 1101, 260, 0, 1027, 1101, 38, 0,
+    1018, 1101, 642, 0, 1029, 1102, 25, 1, 1004, 1101, 23, 0, 1017, 1101, 21, 0,
+    1001, 1102, 20, 1, 1010, 1102, 33, 1, 1015, 1102, 35, 1, 1002, 1102, 1, 1,
+    1021, 1102, 31, 1, 1014, 1101, 895, 0, 1022, 1101, 0, 32, 1011, 1102, 1, 28,
+    1005, 1101, 0, 892, 1023, 1101, 263, 0, 1026, 1102, 1, 27, 1000, 1101, 647, 0,
+    1028, 1101, 0, 34, 1006, 1102, 1, 660, 1025, 109, 9, 1208, -7, 38, 63, 1005,
+    63, 201, 1001, 64, 1, 64, 1106, 0, 203, 4, 187, 1002, 64, 2, 64, 109,
+    4, 2101, 0, -5, 63, 1008, 63, 24, 63, 1005, 63, 227, 1001, 64, 1, 64,
+    1106, 0, 229, 4, 209, 1002, 64, 2, 64, 109, 5, 21107, 40, 41, 0, 1005,
+    1018, 251, 4, 235, 1001, 64, 1, 64, 1105, 1, 251, 1002, 64, 2, 64, 109,
+    18, 2106, 0, -9, 1105, 1, 269, 4, 257, 1001, 64, 1, 64, 1002, 64, 2,
+    64, 109, -40, 1208, 6, 35, 63, 1005, 63, 287, 4, 275, 1105, 1, 291, 1001,
+    64, 1, 64, 1002, 64, 2, 64, 109, 11, 2102, 1, 0, 63, 1008, 63, 35,
+    63, 1005, 63, 315, 1001, 64, 1, 64, 1106, 0, 317, 4, 297, 1002, 64, 2,
+    64, 109, 6, 21107, 41, 40, -3, 1005, 1010, 337, 1001, 64, 1, 64, 1106, 0,
+    339, 4, 323, 1002, 64, 2, 64, 109, -2, 2101, 0, -8, 63, 1008, 63, 24,
+    63, 1005, 63, 365, 4, 345, 1001, 64, 1, 64, 1105, 1, 365, 1002, 64, 2,
+    64, 109, 9, 21102, 42, 1, -3, 1008, 1017, 43, 63, 1005, 63, 385, 1105, 1,
+    391, 4, 371, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -4, 1206, 5, 407,
+    1001, 64, 1, 64, 1105, 1, 409, 4, 397, 1002, 64, 2, 64, 109, 13, 1206,
+    -9, 427, 4, 415, 1001, 64, 1, 64, 1106, 0, 427, 1002, 64, 2, 64, 109,
+    -25, 2107, 27, 1, 63, 1005, 63, 449, 4, 433, 1001, 64, 1, 64, 1106, 0,
+    449, 1002, 64, 2, 64, 109, -3, 1202, -1, 1, 63, 1008, 63, 27, 63, 1005,
+    63, 475, 4, 455, 1001, 64, 1, 64, 1105, 1, 475, 1002, 64, 2, 64, 109,
+    6, 21108, 43, 41, 8, 1005, 1015, 491, 1106, 0, 497, 4, 481, 1001, 64, 1,
+    64, 1002, 64, 2, 64, 109, 6, 1205, 8, 515, 4, 503, 1001, 64, 1, 64,
+    1105, 1, 515, 1002, 64, 2, 64, 109, -11, 1207, 1, 23, 63, 1005, 63, 531,
+    1105, 1, 537, 4, 521, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 1, 2108,
+    24, 0, 63, 1005, 63, 559, 4, 543, 1001, 64, 1, 64, 1105, 1, 559, 1002,
+    64, 2, 64, 109, 12, 21101, 44, 0, 1, 1008, 1016, 44, 63, 1005, 63, 585,
+    4, 565, 1001, 64, 1, 64, 1105, 1, 585, 1002, 64, 2, 64, 109, -23, 2102,
+    1, 8, 63, 1008, 63, 27, 63, 1005, 63, 607, 4, 591, 1105, 1, 611, 1001,
+    64, 1, 64, 1002, 64, 2, 64, 109, 18, 21108, 45, 45, 3, 1005, 1013, 633,
+    4, 617, 1001, 64, 1, 64, 1105, 1, 633, 1002, 64, 2, 64, 109, 11, 2106,
+    0, 7, 4, 639, 1106, 0, 651, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+    -1, 2105, 1, 4, 4, 657, 1001, 64, 1, 64, 1105, 1, 669, 1002, 64, 2,
+    64, 109, -10, 2107, 26, -6, 63, 1005, 63, 685, 1105, 1, 691, 4, 675, 1001,
+    64, 1, 64, 1002, 64, 2, 64, 109, 9, 1205, 1, 703, 1106, 0, 709, 4,
+    697, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -12, 2108, 22, -3, 63, 1005,
+    63, 729, 1001, 64, 1, 64, 1106, 0, 731, 4, 715, 1002, 64, 2, 64, 109,
+    -11, 1207, 10, 35, 63, 1005, 63, 753, 4, 737, 1001, 64, 1, 64, 1106, 0,
+    753, 1002, 64, 2, 64, 109, 9, 21101, 46, 0, 5, 1008, 1010, 43, 63, 1005,
+    63, 773, 1105, 1, 779, 4, 759, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+    -1, 1201, 4, 0, 63, 1008, 63, 26, 63, 1005, 63, 801, 4, 785, 1105, 1,
+    805, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 7, 1201, -8, 0, 63, 1008,
+    63, 22, 63, 1005, 63, 825, 1106, 0, 831, 4, 811, 1001, 64, 1, 64, 1002,
+    64, 2, 64, 109, -1, 1202, -6, 1, 63, 1008, 63, 23, 63, 1005, 63, 855,
+    1001, 64, 1, 64, 1106, 0, 857, 4, 837, 1002, 64, 2, 64, 109, 7, 21102,
+    47, 1, 0, 1008, 1017, 47, 63, 1005, 63, 883, 4, 863, 1001, 64, 1, 64,
+    1106, 0, 883, 1002, 64, 2, 64, 109, 8, 2105, 1, -2, 1106, 0, 901, 4,
+    889, 1001, 64, 1, 64, 4, 64, 99, 21101, 0, 27, 1, 21101, 915, 0, 0,
+    1105, 1, 922, 21201, 1, 20897, 1, 204, 1, 99, 109, 3, 1207, -2, 3, 63,
+    1005, 63, 964, 21201, -2, -1, 1, 21101, 0, 942, 0, 1106, 0, 922, 22101, 0,
+    1, -1, 21201, -2, -3, 1, 21102, 957, 1, 0, 1106, 0, 922, 22201, 1, -1,
+    -2, 1106, 0, 968, 22102, 1, -2, -2, 109, -3, 2105, 1, 0,
+];
+
+const CODE: [u8; 973] = [
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+];
+
+pub fn interpret(mem: &mut Vec<i64>,
+                 read_input: &mut dyn FnMut() -> i64,
+                 write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let mut pc = 0usize;
+    let mut rb = 0i64;
+    let mut modified = (0..IMAGE.len())
+        .any(|addr| CODE[addr] != 0 && mem.read(addr) != IMAGE[addr]);
+    loop {
+        match pc {
+            // mul #34463338, #34463338, [63]
+            0 => {
+                if modified && (mem.read(0) != 1102 || mem.read(1) != 34463338 || mem.read(2) != 34463338 || mem.read(3) != 63) {
+                    break;
+                }
+                let a: i64 = 34463338;
+                let val = get!(a.checked_mul(34463338));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 4;
+            }
+            // lt [63], #34463338, [63]
+            4 => {
+                if modified && (mem.read(4) != 1007 || mem.read(5) != 63 || mem.read(6) != 34463338 || mem.read(7) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a < 34463338) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 8;
+            }
+            // jnz [63], #53
+            8 => {
+                if modified && (mem.read(8) != 1005 || mem.read(9) != 63 || mem.read(10) != 53) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 53 } else { 11 };
+            }
+            // mul #3, #1, [1000]
+            11 => {
+                if modified && (mem.read(11) != 1102 || mem.read(12) != 3 || mem.read(13) != 1 || mem.read(14) != 1000) {
+                    break;
+                }
+                let a: i64 = 3;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1000, val, &mut modified));
+                pc = 15;
+            }
+            // arb #988
+            15 => {
+                if modified && (mem.read(15) != 109 || mem.read(16) != 988) {
+                    break;
+                }
+                rb = get!(rb.checked_add(988));
+                pc = 17;
+            }
+            // arb rb+12
+            17 => {
+                if modified && (mem.read(17) != 209 || mem.read(18) != 12) {
+                    break;
+                }
+                rb = get!(rb.checked_add(get!(load(mem, get!(rb.checked_add(12))))));
+                pc = 19;
+            }
+            // arb [1000]
+            19 => {
+                if modified && (mem.read(19) != 9 || mem.read(20) != 1000) {
+                    break;
+                }
+                rb = get!(rb.checked_add(get!(load(mem, 1000))));
+                pc = 21;
+            }
+            // arb rb+6
+            21 => {
+                if modified && (mem.read(21) != 209 || mem.read(22) != 6) {
+                    break;
+                }
+                rb = get!(rb.checked_add(get!(load(mem, get!(rb.checked_add(6))))));
+                pc = 23;
+            }
+            // arb rb+3
+            23 => {
+                if modified && (mem.read(23) != 209 || mem.read(24) != 3) {
+                    break;
+                }
+                rb = get!(rb.checked_add(get!(load(mem, get!(rb.checked_add(3))))));
+                pc = 25;
+            }
+            // in rb+0
+            25 => {
+                if modified && (mem.read(25) != 203 || mem.read(26) != 0) {
+                    break;
+                }
+                get!(dest(get!(rb.checked_add(0))));
+                let val = read_input();
+                get!(store(mem, get!(rb.checked_add(0)), val, &mut modified));
+                pc = 27;
+            }
+            // eq [1000], #1, [63]
+            27 => {
+                if modified && (mem.read(27) != 1008 || mem.read(28) != 1000 || mem.read(29) != 1 || mem.read(30) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1000));
+                let val = (a == 1) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 31;
+            }
+            // jnz [63], #65
+            31 => {
+                if modified && (mem.read(31) != 1005 || mem.read(32) != 63 || mem.read(33) != 65) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 65 } else { 34 };
+            }
+            // eq [1000], #2, [63]
+            34 => {
+                if modified && (mem.read(34) != 1008 || mem.read(35) != 1000 || mem.read(36) != 2 || mem.read(37) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1000));
+                let val = (a == 2) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 38;
+            }
+            // jnz [63], #904
+            38 => {
+                if modified && (mem.read(38) != 1005 || mem.read(39) != 63 || mem.read(40) != 904) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 904 } else { 41 };
+            }
+            // eq [1000], #0, [63]
+            41 => {
+                if modified && (mem.read(41) != 1008 || mem.read(42) != 1000 || mem.read(43) != 0 || mem.read(44) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1000));
+                let val = (a == 0) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 45;
+            }
+            // jnz [63], #58
+            45 => {
+                if modified && (mem.read(45) != 1005 || mem.read(46) != 63 || mem.read(47) != 58) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 58 } else { 48 };
+            }
+            // out [25]
+            48 => {
+                if modified && (mem.read(48) != 4 || mem.read(49) != 25) {
+                    break;
+                }
+                write_output(get!(load(mem, 25)));
+                pc = 50;
+            }
+            // out #0
+            50 => {
+                if modified && (mem.read(50) != 104 || mem.read(51) != 0) {
+                    break;
+                }
+                write_output(0);
+                pc = 52;
+            }
+            // hlt
+            52 => {
+                if modified && (mem.read(52) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+            // out [0]
+            53 => {
+                if modified && (mem.read(53) != 4 || mem.read(54) != 0) {
+                    break;
+                }
+                write_output(get!(load(mem, 0)));
+                pc = 55;
+            }
+            // out #0
+            55 => {
+                if modified && (mem.read(55) != 104 || mem.read(56) != 0) {
+                    break;
+                }
+                write_output(0);
+                pc = 57;
+            }
+            // hlt
+            57 => {
+                if modified && (mem.read(57) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+            // out [17]
+            58 => {
+                if modified && (mem.read(58) != 4 || mem.read(59) != 17) {
+                    break;
+                }
+                write_output(get!(load(mem, 17)));
+                pc = 60;
+            }
+            // out #0
+            60 => {
+                if modified && (mem.read(60) != 104 || mem.read(61) != 0) {
+                    break;
+                }
+                write_output(0);
+                pc = 62;
+            }
+            // hlt
+            62 => {
+                if modified && (mem.read(62) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+            // add #37, #0, [1012]
+            65 => {
+                if modified && (mem.read(65) != 1101 || mem.read(66) != 37 || mem.read(67) != 0 || mem.read(68) != 1012) {
+                    break;
+                }
+                let a: i64 = 37;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1012, val, &mut modified));
+                pc = 69;
+            }
+            // add #26, #0, [1008]
+            69 => {
+                if modified && (mem.read(69) != 1101 || mem.read(70) != 26 || mem.read(71) != 0 || mem.read(72) != 1008) {
+                    break;
+                }
+                let a: i64 = 26;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1008, val, &mut modified));
+                pc = 73;
+            }
+            // add #0, #39, [1016]
+            73 => {
+                if modified && (mem.read(73) != 1101 || mem.read(74) != 0 || mem.read(75) != 39 || mem.read(76) != 1016) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(39));
+                get!(store(mem, 1016, val, &mut modified));
+                pc = 77;
+            }
+            // add #0, #36, [1007]
+            77 => {
+                if modified && (mem.read(77) != 1101 || mem.read(78) != 0 || mem.read(79) != 36 || mem.read(80) != 1007) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(36));
+                get!(store(mem, 1007, val, &mut modified));
+                pc = 81;
+            }
+            // add #669, #0, [1024]
+            81 => {
+                if modified && (mem.read(81) != 1101 || mem.read(82) != 669 || mem.read(83) != 0 || mem.read(84) != 1024) {
+                    break;
+                }
+                let a: i64 = 669;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1024, val, &mut modified));
+                pc = 85;
+            }
+            // mul #1, #29, [1009]
+            85 => {
+                if modified && (mem.read(85) != 1102 || mem.read(86) != 1 || mem.read(87) != 29 || mem.read(88) != 1009) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(29));
+                get!(store(mem, 1009, val, &mut modified));
+                pc = 89;
+            }
+            // mul #0, #1, [1020]
+            89 => {
+                if modified && (mem.read(89) != 1102 || mem.read(90) != 0 || mem.read(91) != 1 || mem.read(92) != 1020) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1020, val, &mut modified));
+                pc = 93;
+            }
+            // mul #24, #1, [1003]
+            93 => {
+                if modified && (mem.read(93) != 1102 || mem.read(94) != 24 || mem.read(95) != 1 || mem.read(96) != 1003) {
+                    break;
+                }
+                let a: i64 = 24;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1003, val, &mut modified));
+                pc = 97;
+            }
+            // mul #22, #1, [1013]
+            97 => {
+                if modified && (mem.read(97) != 1102 || mem.read(98) != 22 || mem.read(99) != 1 || mem.read(100) != 1013) {
+                    break;
+                }
+                let a: i64 = 22;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1013, val, &mut modified));
+                pc = 101;
+            }
+            // add #0, #30, [1019]
+            101 => {
+                if modified && (mem.read(101) != 1101 || mem.read(102) != 0 || mem.read(103) != 30 || mem.read(104) != 1019) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(30));
+                get!(store(mem, 1019, val, &mut modified));
+                pc = 105;
+            }
+            // add #260, #0, [1027]
+            105 => {
+                if modified && (mem.read(105) != 1101 || mem.read(106) != 260 || mem.read(107) != 0 || mem.read(108) != 1027) {
+                    break;
+                }
+                let a: i64 = 260;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1027, val, &mut modified));
+                pc = 109;
+            }
+            // add #38, #0, [1018]
+            109 => {
+                if modified && (mem.read(109) != 1101 || mem.read(110) != 38 || mem.read(111) != 0 || mem.read(112) != 1018) {
+                    break;
+                }
+                let a: i64 = 38;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1018, val, &mut modified));
+                pc = 113;
+            }
+            // add #642, #0, [1029]
+            113 => {
+                if modified && (mem.read(113) != 1101 || mem.read(114) != 642 || mem.read(115) != 0 || mem.read(116) != 1029) {
+                    break;
+                }
+                let a: i64 = 642;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1029, val, &mut modified));
+                pc = 117;
+            }
+            // mul #25, #1, [1004]
+            117 => {
+                if modified && (mem.read(117) != 1102 || mem.read(118) != 25 || mem.read(119) != 1 || mem.read(120) != 1004) {
+                    break;
+                }
+                let a: i64 = 25;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1004, val, &mut modified));
+                pc = 121;
+            }
+            // add #23, #0, [1017]
+            121 => {
+                if modified && (mem.read(121) != 1101 || mem.read(122) != 23 || mem.read(123) != 0 || mem.read(124) != 1017) {
+                    break;
+                }
+                let a: i64 = 23;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1017, val, &mut modified));
+                pc = 125;
+            }
+            // add #21, #0, [1001]
+            125 => {
+                if modified && (mem.read(125) != 1101 || mem.read(126) != 21 || mem.read(127) != 0 || mem.read(128) != 1001) {
+                    break;
+                }
+                let a: i64 = 21;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1001, val, &mut modified));
+                pc = 129;
+            }
+            // mul #20, #1, [1010]
+            129 => {
+                if modified && (mem.read(129) != 1102 || mem.read(130) != 20 || mem.read(131) != 1 || mem.read(132) != 1010) {
+                    break;
+                }
+                let a: i64 = 20;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1010, val, &mut modified));
+                pc = 133;
+            }
+            // mul #33, #1, [1015]
+            133 => {
+                if modified && (mem.read(133) != 1102 || mem.read(134) != 33 || mem.read(135) != 1 || mem.read(136) != 1015) {
+                    break;
+                }
+                let a: i64 = 33;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1015, val, &mut modified));
+                pc = 137;
+            }
+            // mul #35, #1, [1002]
+            137 => {
+                if modified && (mem.read(137) != 1102 || mem.read(138) != 35 || mem.read(139) != 1 || mem.read(140) != 1002) {
+                    break;
+                }
+                let a: i64 = 35;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1002, val, &mut modified));
+                pc = 141;
+            }
+            // mul #1, #1, [1021]
+            141 => {
+                if modified && (mem.read(141) != 1102 || mem.read(142) != 1 || mem.read(143) != 1 || mem.read(144) != 1021) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1021, val, &mut modified));
+                pc = 145;
+            }
+            // mul #31, #1, [1014]
+            145 => {
+                if modified && (mem.read(145) != 1102 || mem.read(146) != 31 || mem.read(147) != 1 || mem.read(148) != 1014) {
+                    break;
+                }
+                let a: i64 = 31;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 1014, val, &mut modified));
+                pc = 149;
+            }
+            // add #895, #0, [1022]
+            149 => {
+                if modified && (mem.read(149) != 1101 || mem.read(150) != 895 || mem.read(151) != 0 || mem.read(152) != 1022) {
+                    break;
+                }
+                let a: i64 = 895;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1022, val, &mut modified));
+                pc = 153;
+            }
+            // add #0, #32, [1011]
+            153 => {
+                if modified && (mem.read(153) != 1101 || mem.read(154) != 0 || mem.read(155) != 32 || mem.read(156) != 1011) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(32));
+                get!(store(mem, 1011, val, &mut modified));
+                pc = 157;
+            }
+            // mul #1, #28, [1005]
+            157 => {
+                if modified && (mem.read(157) != 1102 || mem.read(158) != 1 || mem.read(159) != 28 || mem.read(160) != 1005) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(28));
+                get!(store(mem, 1005, val, &mut modified));
+                pc = 161;
+            }
+            // add #0, #892, [1023]
+            161 => {
+                if modified && (mem.read(161) != 1101 || mem.read(162) != 0 || mem.read(163) != 892 || mem.read(164) != 1023) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(892));
+                get!(store(mem, 1023, val, &mut modified));
+                pc = 165;
+            }
+            // add #263, #0, [1026]
+            165 => {
+                if modified && (mem.read(165) != 1101 || mem.read(166) != 263 || mem.read(167) != 0 || mem.read(168) != 1026) {
+                    break;
+                }
+                let a: i64 = 263;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1026, val, &mut modified));
+                pc = 169;
+            }
+            // mul #1, #27, [1000]
+            169 => {
+                if modified && (mem.read(169) != 1102 || mem.read(170) != 1 || mem.read(171) != 27 || mem.read(172) != 1000) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(27));
+                get!(store(mem, 1000, val, &mut modified));
+                pc = 173;
+            }
+            // add #647, #0, [1028]
+            173 => {
+                if modified && (mem.read(173) != 1101 || mem.read(174) != 647 || mem.read(175) != 0 || mem.read(176) != 1028) {
+                    break;
+                }
+                let a: i64 = 647;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 1028, val, &mut modified));
+                pc = 177;
+            }
+            // add #0, #34, [1006]
+            177 => {
+                if modified && (mem.read(177) != 1101 || mem.read(178) != 0 || mem.read(179) != 34 || mem.read(180) != 1006) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(34));
+                get!(store(mem, 1006, val, &mut modified));
+                pc = 181;
+            }
+            // mul #1, #660, [1025]
+            181 => {
+                if modified && (mem.read(181) != 1102 || mem.read(182) != 1 || mem.read(183) != 660 || mem.read(184) != 1025) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(660));
+                get!(store(mem, 1025, val, &mut modified));
+                pc = 185;
+            }
+            // arb #9
+            185 => {
+                if modified && (mem.read(185) != 109 || mem.read(186) != 9) {
+                    break;
+                }
+                rb = get!(rb.checked_add(9));
+                pc = 187;
+            }
+            // eq rb-7, #38, [63]
+            187 => {
+                if modified && (mem.read(187) != 1208 || mem.read(188) != -7 || mem.read(189) != 38 || mem.read(190) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-7))));
+                let val = (a == 38) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 191;
+            }
+            // jnz [63], #201
+            191 => {
+                if modified && (mem.read(191) != 1005 || mem.read(192) != 63 || mem.read(193) != 201) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 201 } else { 194 };
+            }
+            // add [64], #1, [64]
+            194 => {
+                if modified && (mem.read(194) != 1001 || mem.read(195) != 64 || mem.read(196) != 1 || mem.read(197) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 198;
+            }
+            // jz #0, #203
+            198 => {
+                if modified && (mem.read(198) != 1106 || mem.read(199) != 0 || mem.read(200) != 203) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 203 } else { 201 };
+            }
+            // out [187]
+            201 => {
+                if modified && (mem.read(201) != 4 || mem.read(202) != 187) {
+                    break;
+                }
+                write_output(get!(load(mem, 187)));
+                pc = 203;
+            }
+            // mul [64], #2, [64]
+            203 => {
+                if modified && (mem.read(203) != 1002 || mem.read(204) != 64 || mem.read(205) != 2 || mem.read(206) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 207;
+            }
+            // arb #4
+            207 => {
+                if modified && (mem.read(207) != 109 || mem.read(208) != 4) {
+                    break;
+                }
+                rb = get!(rb.checked_add(4));
+                pc = 209;
+            }
+            // add #0, rb-5, [63]
+            209 => {
+                if modified && (mem.read(209) != 2101 || mem.read(210) != 0 || mem.read(211) != -5 || mem.read(212) != 63) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(get!(load(mem, get!(rb.checked_add(-5))))));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 213;
+            }
+            // eq [63], #24, [63]
+            213 => {
+                if modified && (mem.read(213) != 1008 || mem.read(214) != 63 || mem.read(215) != 24 || mem.read(216) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 24) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 217;
+            }
+            // jnz [63], #227
+            217 => {
+                if modified && (mem.read(217) != 1005 || mem.read(218) != 63 || mem.read(219) != 227) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 227 } else { 220 };
+            }
+            // add [64], #1, [64]
+            220 => {
+                if modified && (mem.read(220) != 1001 || mem.read(221) != 64 || mem.read(222) != 1 || mem.read(223) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 224;
+            }
+            // jz #0, #229
+            224 => {
+                if modified && (mem.read(224) != 1106 || mem.read(225) != 0 || mem.read(226) != 229) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 229 } else { 227 };
+            }
+            // out [209]
+            227 => {
+                if modified && (mem.read(227) != 4 || mem.read(228) != 209) {
+                    break;
+                }
+                write_output(get!(load(mem, 209)));
+                pc = 229;
+            }
+            // mul [64], #2, [64]
+            229 => {
+                if modified && (mem.read(229) != 1002 || mem.read(230) != 64 || mem.read(231) != 2 || mem.read(232) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 233;
+            }
+            // arb #5
+            233 => {
+                if modified && (mem.read(233) != 109 || mem.read(234) != 5) {
+                    break;
+                }
+                rb = get!(rb.checked_add(5));
+                pc = 235;
+            }
+            // lt #40, #41, rb+0
+            235 => {
+                if modified && (mem.read(235) != 21107 || mem.read(236) != 40 || mem.read(237) != 41 || mem.read(238) != 0) {
+                    break;
+                }
+                let a: i64 = 40;
+                let val = (a < 41) as i64;
+                get!(store(mem, get!(rb.checked_add(0)), val, &mut modified));
+                pc = 239;
+            }
+            // jnz [1018], #251
+            239 => {
+                if modified && (mem.read(239) != 1005 || mem.read(240) != 1018 || mem.read(241) != 251) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1018));
+                pc = if a != 0 { 251 } else { 242 };
+            }
+            // out [235]
+            242 => {
+                if modified && (mem.read(242) != 4 || mem.read(243) != 235) {
+                    break;
+                }
+                write_output(get!(load(mem, 235)));
+                pc = 244;
+            }
+            // add [64], #1, [64]
+            244 => {
+                if modified && (mem.read(244) != 1001 || mem.read(245) != 64 || mem.read(246) != 1 || mem.read(247) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 248;
+            }
+            // jnz #1, #251
+            248 => {
+                if modified && (mem.read(248) != 1105 || mem.read(249) != 1 || mem.read(250) != 251) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 251 } else { 251 };
+            }
+            // mul [64], #2, [64]
+            251 => {
+                if modified && (mem.read(251) != 1002 || mem.read(252) != 64 || mem.read(253) != 2 || mem.read(254) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 255;
+            }
+            // arb #18
+            255 => {
+                if modified && (mem.read(255) != 109 || mem.read(256) != 18) {
+                    break;
+                }
+                rb = get!(rb.checked_add(18));
+                pc = 257;
+            }
+            // jz #0, rb-9
+            257 => {
+                if modified && (mem.read(257) != 2106 || mem.read(258) != 0 || mem.read(259) != -9) {
+                    break;
+                }
+                let a: i64 = 0;
+                let b: i64 = get!(load(mem, get!(rb.checked_add(-9))));
+                pc = if a == 0 { get!(jump(b)) } else { 260 };
+            }
+            // jnz #1, #269
+            260 => {
+                if modified && (mem.read(260) != 1105 || mem.read(261) != 1 || mem.read(262) != 269) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 269 } else { 263 };
+            }
+            // out [257]
+            263 => {
+                if modified && (mem.read(263) != 4 || mem.read(264) != 257) {
+                    break;
+                }
+                write_output(get!(load(mem, 257)));
+                pc = 265;
+            }
+            // add [64], #1, [64]
+            265 => {
+                if modified && (mem.read(265) != 1001 || mem.read(266) != 64 || mem.read(267) != 1 || mem.read(268) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 269;
+            }
+            // mul [64], #2, [64]
+            269 => {
+                if modified && (mem.read(269) != 1002 || mem.read(270) != 64 || mem.read(271) != 2 || mem.read(272) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 273;
+            }
+            // arb #-40
+            273 => {
+                if modified && (mem.read(273) != 109 || mem.read(274) != -40) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-40));
+                pc = 275;
+            }
+            // eq rb+6, #35, [63]
+            275 => {
+                if modified && (mem.read(275) != 1208 || mem.read(276) != 6 || mem.read(277) != 35 || mem.read(278) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(6))));
+                let val = (a == 35) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 279;
+            }
+            // jnz [63], #287
+            279 => {
+                if modified && (mem.read(279) != 1005 || mem.read(280) != 63 || mem.read(281) != 287) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 287 } else { 282 };
+            }
+            // out [275]
+            282 => {
+                if modified && (mem.read(282) != 4 || mem.read(283) != 275) {
+                    break;
+                }
+                write_output(get!(load(mem, 275)));
+                pc = 284;
+            }
+            // jnz #1, #291
+            284 => {
+                if modified && (mem.read(284) != 1105 || mem.read(285) != 1 || mem.read(286) != 291) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 291 } else { 287 };
+            }
+            // add [64], #1, [64]
+            287 => {
+                if modified && (mem.read(287) != 1001 || mem.read(288) != 64 || mem.read(289) != 1 || mem.read(290) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 291;
+            }
+            // mul [64], #2, [64]
+            291 => {
+                if modified && (mem.read(291) != 1002 || mem.read(292) != 64 || mem.read(293) != 2 || mem.read(294) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 295;
+            }
+            // arb #11
+            295 => {
+                if modified && (mem.read(295) != 109 || mem.read(296) != 11) {
+                    break;
+                }
+                rb = get!(rb.checked_add(11));
+                pc = 297;
+            }
+            // mul #1, rb+0, [63]
+            297 => {
+                if modified && (mem.read(297) != 2102 || mem.read(298) != 1 || mem.read(299) != 0 || mem.read(300) != 63) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(get!(load(mem, get!(rb.checked_add(0))))));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 301;
+            }
+            // eq [63], #35, [63]
+            301 => {
+                if modified && (mem.read(301) != 1008 || mem.read(302) != 63 || mem.read(303) != 35 || mem.read(304) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 35) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 305;
+            }
+            // jnz [63], #315
+            305 => {
+                if modified && (mem.read(305) != 1005 || mem.read(306) != 63 || mem.read(307) != 315) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 315 } else { 308 };
+            }
+            // add [64], #1, [64]
+            308 => {
+                if modified && (mem.read(308) != 1001 || mem.read(309) != 64 || mem.read(310) != 1 || mem.read(311) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 312;
+            }
+            // jz #0, #317
+            312 => {
+                if modified && (mem.read(312) != 1106 || mem.read(313) != 0 || mem.read(314) != 317) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 317 } else { 315 };
+            }
+            // out [297]
+            315 => {
+                if modified && (mem.read(315) != 4 || mem.read(316) != 297) {
+                    break;
+                }
+                write_output(get!(load(mem, 297)));
+                pc = 317;
+            }
+            // mul [64], #2, [64]
+            317 => {
+                if modified && (mem.read(317) != 1002 || mem.read(318) != 64 || mem.read(319) != 2 || mem.read(320) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 321;
+            }
+            // arb #6
+            321 => {
+                if modified && (mem.read(321) != 109 || mem.read(322) != 6) {
+                    break;
+                }
+                rb = get!(rb.checked_add(6));
+                pc = 323;
+            }
+            // lt #41, #40, rb-3
+            323 => {
+                if modified && (mem.read(323) != 21107 || mem.read(324) != 41 || mem.read(325) != 40 || mem.read(326) != -3) {
+                    break;
+                }
+                let a: i64 = 41;
+                let val = (a < 40) as i64;
+                get!(store(mem, get!(rb.checked_add(-3)), val, &mut modified));
+                pc = 327;
+            }
+            // jnz [1010], #337
+            327 => {
+                if modified && (mem.read(327) != 1005 || mem.read(328) != 1010 || mem.read(329) != 337) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1010));
+                pc = if a != 0 { 337 } else { 330 };
+            }
+            // add [64], #1, [64]
+            330 => {
+                if modified && (mem.read(330) != 1001 || mem.read(331) != 64 || mem.read(332) != 1 || mem.read(333) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 334;
+            }
+            // jz #0, #339
+            334 => {
+                if modified && (mem.read(334) != 1106 || mem.read(335) != 0 || mem.read(336) != 339) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 339 } else { 337 };
+            }
+            // out [323]
+            337 => {
+                if modified && (mem.read(337) != 4 || mem.read(338) != 323) {
+                    break;
+                }
+                write_output(get!(load(mem, 323)));
+                pc = 339;
+            }
+            // mul [64], #2, [64]
+            339 => {
+                if modified && (mem.read(339) != 1002 || mem.read(340) != 64 || mem.read(341) != 2 || mem.read(342) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 343;
+            }
+            // arb #-2
+            343 => {
+                if modified && (mem.read(343) != 109 || mem.read(344) != -2) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-2));
+                pc = 345;
+            }
+            // add #0, rb-8, [63]
+            345 => {
+                if modified && (mem.read(345) != 2101 || mem.read(346) != 0 || mem.read(347) != -8 || mem.read(348) != 63) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(get!(load(mem, get!(rb.checked_add(-8))))));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 349;
+            }
+            // eq [63], #24, [63]
+            349 => {
+                if modified && (mem.read(349) != 1008 || mem.read(350) != 63 || mem.read(351) != 24 || mem.read(352) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 24) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 353;
+            }
+            // jnz [63], #365
+            353 => {
+                if modified && (mem.read(353) != 1005 || mem.read(354) != 63 || mem.read(355) != 365) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 365 } else { 356 };
+            }
+            // out [345]
+            356 => {
+                if modified && (mem.read(356) != 4 || mem.read(357) != 345) {
+                    break;
+                }
+                write_output(get!(load(mem, 345)));
+                pc = 358;
+            }
+            // add [64], #1, [64]
+            358 => {
+                if modified && (mem.read(358) != 1001 || mem.read(359) != 64 || mem.read(360) != 1 || mem.read(361) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 362;
+            }
+            // jnz #1, #365
+            362 => {
+                if modified && (mem.read(362) != 1105 || mem.read(363) != 1 || mem.read(364) != 365) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 365 } else { 365 };
+            }
+            // mul [64], #2, [64]
+            365 => {
+                if modified && (mem.read(365) != 1002 || mem.read(366) != 64 || mem.read(367) != 2 || mem.read(368) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 369;
+            }
+            // arb #9
+            369 => {
+                if modified && (mem.read(369) != 109 || mem.read(370) != 9) {
+                    break;
+                }
+                rb = get!(rb.checked_add(9));
+                pc = 371;
+            }
+            // mul #42, #1, rb-3
+            371 => {
+                if modified && (mem.read(371) != 21102 || mem.read(372) != 42 || mem.read(373) != 1 || mem.read(374) != -3) {
+                    break;
+                }
+                let a: i64 = 42;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, get!(rb.checked_add(-3)), val, &mut modified));
+                pc = 375;
+            }
+            // eq [1017], #43, [63]
+            375 => {
+                if modified && (mem.read(375) != 1008 || mem.read(376) != 1017 || mem.read(377) != 43 || mem.read(378) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1017));
+                let val = (a == 43) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 379;
+            }
+            // jnz [63], #385
+            379 => {
+                if modified && (mem.read(379) != 1005 || mem.read(380) != 63 || mem.read(381) != 385) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 385 } else { 382 };
+            }
+            // jnz #1, #391
+            382 => {
+                if modified && (mem.read(382) != 1105 || mem.read(383) != 1 || mem.read(384) != 391) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 391 } else { 385 };
+            }
+            // out [371]
+            385 => {
+                if modified && (mem.read(385) != 4 || mem.read(386) != 371) {
+                    break;
+                }
+                write_output(get!(load(mem, 371)));
+                pc = 387;
+            }
+            // add [64], #1, [64]
+            387 => {
+                if modified && (mem.read(387) != 1001 || mem.read(388) != 64 || mem.read(389) != 1 || mem.read(390) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 391;
+            }
+            // mul [64], #2, [64]
+            391 => {
+                if modified && (mem.read(391) != 1002 || mem.read(392) != 64 || mem.read(393) != 2 || mem.read(394) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 395;
+            }
+            // arb #-4
+            395 => {
+                if modified && (mem.read(395) != 109 || mem.read(396) != -4) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-4));
+                pc = 397;
+            }
+            // jz rb+5, #407
+            397 => {
+                if modified && (mem.read(397) != 1206 || mem.read(398) != 5 || mem.read(399) != 407) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(5))));
+                pc = if a == 0 { 407 } else { 400 };
+            }
+            // add [64], #1, [64]
+            400 => {
+                if modified && (mem.read(400) != 1001 || mem.read(401) != 64 || mem.read(402) != 1 || mem.read(403) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 404;
+            }
+            // jnz #1, #409
+            404 => {
+                if modified && (mem.read(404) != 1105 || mem.read(405) != 1 || mem.read(406) != 409) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 409 } else { 407 };
+            }
+            // out [397]
+            407 => {
+                if modified && (mem.read(407) != 4 || mem.read(408) != 397) {
+                    break;
+                }
+                write_output(get!(load(mem, 397)));
+                pc = 409;
+            }
+            // mul [64], #2, [64]
+            409 => {
+                if modified && (mem.read(409) != 1002 || mem.read(410) != 64 || mem.read(411) != 2 || mem.read(412) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 413;
+            }
+            // arb #13
+            413 => {
+                if modified && (mem.read(413) != 109 || mem.read(414) != 13) {
+                    break;
+                }
+                rb = get!(rb.checked_add(13));
+                pc = 415;
+            }
+            // jz rb-9, #427
+            415 => {
+                if modified && (mem.read(415) != 1206 || mem.read(416) != -9 || mem.read(417) != 427) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-9))));
+                pc = if a == 0 { 427 } else { 418 };
+            }
+            // out [415]
+            418 => {
+                if modified && (mem.read(418) != 4 || mem.read(419) != 415) {
+                    break;
+                }
+                write_output(get!(load(mem, 415)));
+                pc = 420;
+            }
+            // add [64], #1, [64]
+            420 => {
+                if modified && (mem.read(420) != 1001 || mem.read(421) != 64 || mem.read(422) != 1 || mem.read(423) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 424;
+            }
+            // jz #0, #427
+            424 => {
+                if modified && (mem.read(424) != 1106 || mem.read(425) != 0 || mem.read(426) != 427) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 427 } else { 427 };
+            }
+            // mul [64], #2, [64]
+            427 => {
+                if modified && (mem.read(427) != 1002 || mem.read(428) != 64 || mem.read(429) != 2 || mem.read(430) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 431;
+            }
+            // arb #-25
+            431 => {
+                if modified && (mem.read(431) != 109 || mem.read(432) != -25) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-25));
+                pc = 433;
+            }
+            // lt #27, rb+1, [63]
+            433 => {
+                if modified && (mem.read(433) != 2107 || mem.read(434) != 27 || mem.read(435) != 1 || mem.read(436) != 63) {
+                    break;
+                }
+                let a: i64 = 27;
+                let val = (a < get!(load(mem, get!(rb.checked_add(1))))) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 437;
+            }
+            // jnz [63], #449
+            437 => {
+                if modified && (mem.read(437) != 1005 || mem.read(438) != 63 || mem.read(439) != 449) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 449 } else { 440 };
+            }
+            // out [433]
+            440 => {
+                if modified && (mem.read(440) != 4 || mem.read(441) != 433) {
+                    break;
+                }
+                write_output(get!(load(mem, 433)));
+                pc = 442;
+            }
+            // add [64], #1, [64]
+            442 => {
+                if modified && (mem.read(442) != 1001 || mem.read(443) != 64 || mem.read(444) != 1 || mem.read(445) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 446;
+            }
+            // jz #0, #449
+            446 => {
+                if modified && (mem.read(446) != 1106 || mem.read(447) != 0 || mem.read(448) != 449) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 449 } else { 449 };
+            }
+            // mul [64], #2, [64]
+            449 => {
+                if modified && (mem.read(449) != 1002 || mem.read(450) != 64 || mem.read(451) != 2 || mem.read(452) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 453;
+            }
+            // arb #-3
+            453 => {
+                if modified && (mem.read(453) != 109 || mem.read(454) != -3) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-3));
+                pc = 455;
+            }
+            // mul rb-1, #1, [63]
+            455 => {
+                if modified && (mem.read(455) != 1202 || mem.read(456) != -1 || mem.read(457) != 1 || mem.read(458) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-1))));
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 459;
+            }
+            // eq [63], #27, [63]
+            459 => {
+                if modified && (mem.read(459) != 1008 || mem.read(460) != 63 || mem.read(461) != 27 || mem.read(462) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 27) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 463;
+            }
+            // jnz [63], #475
+            463 => {
+                if modified && (mem.read(463) != 1005 || mem.read(464) != 63 || mem.read(465) != 475) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 475 } else { 466 };
+            }
+            // out [455]
+            466 => {
+                if modified && (mem.read(466) != 4 || mem.read(467) != 455) {
+                    break;
+                }
+                write_output(get!(load(mem, 455)));
+                pc = 468;
+            }
+            // add [64], #1, [64]
+            468 => {
+                if modified && (mem.read(468) != 1001 || mem.read(469) != 64 || mem.read(470) != 1 || mem.read(471) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 472;
+            }
+            // jnz #1, #475
+            472 => {
+                if modified && (mem.read(472) != 1105 || mem.read(473) != 1 || mem.read(474) != 475) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 475 } else { 475 };
+            }
+            // mul [64], #2, [64]
+            475 => {
+                if modified && (mem.read(475) != 1002 || mem.read(476) != 64 || mem.read(477) != 2 || mem.read(478) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 479;
+            }
+            // arb #6
+            479 => {
+                if modified && (mem.read(479) != 109 || mem.read(480) != 6) {
+                    break;
+                }
+                rb = get!(rb.checked_add(6));
+                pc = 481;
+            }
+            // eq #43, #41, rb+8
+            481 => {
+                if modified && (mem.read(481) != 21108 || mem.read(482) != 43 || mem.read(483) != 41 || mem.read(484) != 8) {
+                    break;
+                }
+                let a: i64 = 43;
+                let val = (a == 41) as i64;
+                get!(store(mem, get!(rb.checked_add(8)), val, &mut modified));
+                pc = 485;
+            }
+            // jnz [1015], #491
+            485 => {
+                if modified && (mem.read(485) != 1005 || mem.read(486) != 1015 || mem.read(487) != 491) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1015));
+                pc = if a != 0 { 491 } else { 488 };
+            }
+            // jz #0, #497
+            488 => {
+                if modified && (mem.read(488) != 1106 || mem.read(489) != 0 || mem.read(490) != 497) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 497 } else { 491 };
+            }
+            // out [481]
+            491 => {
+                if modified && (mem.read(491) != 4 || mem.read(492) != 481) {
+                    break;
+                }
+                write_output(get!(load(mem, 481)));
+                pc = 493;
+            }
+            // add [64], #1, [64]
+            493 => {
+                if modified && (mem.read(493) != 1001 || mem.read(494) != 64 || mem.read(495) != 1 || mem.read(496) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 497;
+            }
+            // mul [64], #2, [64]
+            497 => {
+                if modified && (mem.read(497) != 1002 || mem.read(498) != 64 || mem.read(499) != 2 || mem.read(500) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 501;
+            }
+            // arb #6
+            501 => {
+                if modified && (mem.read(501) != 109 || mem.read(502) != 6) {
+                    break;
+                }
+                rb = get!(rb.checked_add(6));
+                pc = 503;
+            }
+            // jnz rb+8, #515
+            503 => {
+                if modified && (mem.read(503) != 1205 || mem.read(504) != 8 || mem.read(505) != 515) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(8))));
+                pc = if a != 0 { 515 } else { 506 };
+            }
+            // out [503]
+            506 => {
+                if modified && (mem.read(506) != 4 || mem.read(507) != 503) {
+                    break;
+                }
+                write_output(get!(load(mem, 503)));
+                pc = 508;
+            }
+            // add [64], #1, [64]
+            508 => {
+                if modified && (mem.read(508) != 1001 || mem.read(509) != 64 || mem.read(510) != 1 || mem.read(511) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 512;
+            }
+            // jnz #1, #515
+            512 => {
+                if modified && (mem.read(512) != 1105 || mem.read(513) != 1 || mem.read(514) != 515) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 515 } else { 515 };
+            }
+            // mul [64], #2, [64]
+            515 => {
+                if modified && (mem.read(515) != 1002 || mem.read(516) != 64 || mem.read(517) != 2 || mem.read(518) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 519;
+            }
+            // arb #-11
+            519 => {
+                if modified && (mem.read(519) != 109 || mem.read(520) != -11) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-11));
+                pc = 521;
+            }
+            // lt rb+1, #23, [63]
+            521 => {
+                if modified && (mem.read(521) != 1207 || mem.read(522) != 1 || mem.read(523) != 23 || mem.read(524) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(1))));
+                let val = (a < 23) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 525;
+            }
+            // jnz [63], #531
+            525 => {
+                if modified && (mem.read(525) != 1005 || mem.read(526) != 63 || mem.read(527) != 531) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 531 } else { 528 };
+            }
+            // jnz #1, #537
+            528 => {
+                if modified && (mem.read(528) != 1105 || mem.read(529) != 1 || mem.read(530) != 537) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 537 } else { 531 };
+            }
+            // out [521]
+            531 => {
+                if modified && (mem.read(531) != 4 || mem.read(532) != 521) {
+                    break;
+                }
+                write_output(get!(load(mem, 521)));
+                pc = 533;
+            }
+            // add [64], #1, [64]
+            533 => {
+                if modified && (mem.read(533) != 1001 || mem.read(534) != 64 || mem.read(535) != 1 || mem.read(536) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 537;
+            }
+            // mul [64], #2, [64]
+            537 => {
+                if modified && (mem.read(537) != 1002 || mem.read(538) != 64 || mem.read(539) != 2 || mem.read(540) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 541;
+            }
+            // arb #1
+            541 => {
+                if modified && (mem.read(541) != 109 || mem.read(542) != 1) {
+                    break;
+                }
+                rb = get!(rb.checked_add(1));
+                pc = 543;
+            }
+            // eq #24, rb+0, [63]
+            543 => {
+                if modified && (mem.read(543) != 2108 || mem.read(544) != 24 || mem.read(545) != 0 || mem.read(546) != 63) {
+                    break;
+                }
+                let a: i64 = 24;
+                let val = (a == get!(load(mem, get!(rb.checked_add(0))))) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 547;
+            }
+            // jnz [63], #559
+            547 => {
+                if modified && (mem.read(547) != 1005 || mem.read(548) != 63 || mem.read(549) != 559) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 559 } else { 550 };
+            }
+            // out [543]
+            550 => {
+                if modified && (mem.read(550) != 4 || mem.read(551) != 543) {
+                    break;
+                }
+                write_output(get!(load(mem, 543)));
+                pc = 552;
+            }
+            // add [64], #1, [64]
+            552 => {
+                if modified && (mem.read(552) != 1001 || mem.read(553) != 64 || mem.read(554) != 1 || mem.read(555) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 556;
+            }
+            // jnz #1, #559
+            556 => {
+                if modified && (mem.read(556) != 1105 || mem.read(557) != 1 || mem.read(558) != 559) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 559 } else { 559 };
+            }
+            // mul [64], #2, [64]
+            559 => {
+                if modified && (mem.read(559) != 1002 || mem.read(560) != 64 || mem.read(561) != 2 || mem.read(562) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 563;
+            }
+            // arb #12
+            563 => {
+                if modified && (mem.read(563) != 109 || mem.read(564) != 12) {
+                    break;
+                }
+                rb = get!(rb.checked_add(12));
+                pc = 565;
+            }
+            // add #44, #0, rb+1
+            565 => {
+                if modified && (mem.read(565) != 21101 || mem.read(566) != 44 || mem.read(567) != 0 || mem.read(568) != 1) {
+                    break;
+                }
+                let a: i64 = 44;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, get!(rb.checked_add(1)), val, &mut modified));
+                pc = 569;
+            }
+            // eq [1016], #44, [63]
+            569 => {
+                if modified && (mem.read(569) != 1008 || mem.read(570) != 1016 || mem.read(571) != 44 || mem.read(572) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1016));
+                let val = (a == 44) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 573;
+            }
+            // jnz [63], #585
+            573 => {
+                if modified && (mem.read(573) != 1005 || mem.read(574) != 63 || mem.read(575) != 585) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 585 } else { 576 };
+            }
+            // out [565]
+            576 => {
+                if modified && (mem.read(576) != 4 || mem.read(577) != 565) {
+                    break;
+                }
+                write_output(get!(load(mem, 565)));
+                pc = 578;
+            }
+            // add [64], #1, [64]
+            578 => {
+                if modified && (mem.read(578) != 1001 || mem.read(579) != 64 || mem.read(580) != 1 || mem.read(581) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 582;
+            }
+            // jnz #1, #585
+            582 => {
+                if modified && (mem.read(582) != 1105 || mem.read(583) != 1 || mem.read(584) != 585) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 585 } else { 585 };
+            }
+            // mul [64], #2, [64]
+            585 => {
+                if modified && (mem.read(585) != 1002 || mem.read(586) != 64 || mem.read(587) != 2 || mem.read(588) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 589;
+            }
+            // arb #-23
+            589 => {
+                if modified && (mem.read(589) != 109 || mem.read(590) != -23) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-23));
+                pc = 591;
+            }
+            // mul #1, rb+8, [63]
+            591 => {
+                if modified && (mem.read(591) != 2102 || mem.read(592) != 1 || mem.read(593) != 8 || mem.read(594) != 63) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(get!(load(mem, get!(rb.checked_add(8))))));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 595;
+            }
+            // eq [63], #27, [63]
+            595 => {
+                if modified && (mem.read(595) != 1008 || mem.read(596) != 63 || mem.read(597) != 27 || mem.read(598) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 27) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 599;
+            }
+            // jnz [63], #607
+            599 => {
+                if modified && (mem.read(599) != 1005 || mem.read(600) != 63 || mem.read(601) != 607) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 607 } else { 602 };
+            }
+            // out [591]
+            602 => {
+                if modified && (mem.read(602) != 4 || mem.read(603) != 591) {
+                    break;
+                }
+                write_output(get!(load(mem, 591)));
+                pc = 604;
+            }
+            // jnz #1, #611
+            604 => {
+                if modified && (mem.read(604) != 1105 || mem.read(605) != 1 || mem.read(606) != 611) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 611 } else { 607 };
+            }
+            // add [64], #1, [64]
+            607 => {
+                if modified && (mem.read(607) != 1001 || mem.read(608) != 64 || mem.read(609) != 1 || mem.read(610) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 611;
+            }
+            // mul [64], #2, [64]
+            611 => {
+                if modified && (mem.read(611) != 1002 || mem.read(612) != 64 || mem.read(613) != 2 || mem.read(614) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 615;
+            }
+            // arb #18
+            615 => {
+                if modified && (mem.read(615) != 109 || mem.read(616) != 18) {
+                    break;
+                }
+                rb = get!(rb.checked_add(18));
+                pc = 617;
+            }
+            // eq #45, #45, rb+3
+            617 => {
+                if modified && (mem.read(617) != 21108 || mem.read(618) != 45 || mem.read(619) != 45 || mem.read(620) != 3) {
+                    break;
+                }
+                let a: i64 = 45;
+                let val = (a == 45) as i64;
+                get!(store(mem, get!(rb.checked_add(3)), val, &mut modified));
+                pc = 621;
+            }
+            // jnz [1013], #633
+            621 => {
+                if modified && (mem.read(621) != 1005 || mem.read(622) != 1013 || mem.read(623) != 633) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1013));
+                pc = if a != 0 { 633 } else { 624 };
+            }
+            // out [617]
+            624 => {
+                if modified && (mem.read(624) != 4 || mem.read(625) != 617) {
+                    break;
+                }
+                write_output(get!(load(mem, 617)));
+                pc = 626;
+            }
+            // add [64], #1, [64]
+            626 => {
+                if modified && (mem.read(626) != 1001 || mem.read(627) != 64 || mem.read(628) != 1 || mem.read(629) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 630;
+            }
+            // jnz #1, #633
+            630 => {
+                if modified && (mem.read(630) != 1105 || mem.read(631) != 1 || mem.read(632) != 633) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 633 } else { 633 };
+            }
+            // mul [64], #2, [64]
+            633 => {
+                if modified && (mem.read(633) != 1002 || mem.read(634) != 64 || mem.read(635) != 2 || mem.read(636) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 637;
+            }
+            // arb #11
+            637 => {
+                if modified && (mem.read(637) != 109 || mem.read(638) != 11) {
+                    break;
+                }
+                rb = get!(rb.checked_add(11));
+                pc = 639;
+            }
+            // jz #0, rb+7
+            639 => {
+                if modified && (mem.read(639) != 2106 || mem.read(640) != 0 || mem.read(641) != 7) {
+                    break;
+                }
+                let a: i64 = 0;
+                let b: i64 = get!(load(mem, get!(rb.checked_add(7))));
+                pc = if a == 0 { get!(jump(b)) } else { 642 };
+            }
+            // out [639]
+            642 => {
+                if modified && (mem.read(642) != 4 || mem.read(643) != 639) {
+                    break;
+                }
+                write_output(get!(load(mem, 639)));
+                pc = 644;
+            }
+            // jz #0, #651
+            644 => {
+                if modified && (mem.read(644) != 1106 || mem.read(645) != 0 || mem.read(646) != 651) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 651 } else { 647 };
+            }
+            // add [64], #1, [64]
+            647 => {
+                if modified && (mem.read(647) != 1001 || mem.read(648) != 64 || mem.read(649) != 1 || mem.read(650) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 651;
+            }
+            // mul [64], #2, [64]
+            651 => {
+                if modified && (mem.read(651) != 1002 || mem.read(652) != 64 || mem.read(653) != 2 || mem.read(654) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 655;
+            }
+            // arb #-1
+            655 => {
+                if modified && (mem.read(655) != 109 || mem.read(656) != -1) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-1));
+                pc = 657;
+            }
+            // jnz #1, rb+4
+            657 => {
+                if modified && (mem.read(657) != 2105 || mem.read(658) != 1 || mem.read(659) != 4) {
+                    break;
+                }
+                let a: i64 = 1;
+                let b: i64 = get!(load(mem, get!(rb.checked_add(4))));
+                pc = if a != 0 { get!(jump(b)) } else { 660 };
+            }
+            // out [657]
+            660 => {
+                if modified && (mem.read(660) != 4 || mem.read(661) != 657) {
+                    break;
+                }
+                write_output(get!(load(mem, 657)));
+                pc = 662;
+            }
+            // add [64], #1, [64]
+            662 => {
+                if modified && (mem.read(662) != 1001 || mem.read(663) != 64 || mem.read(664) != 1 || mem.read(665) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 666;
+            }
+            // jnz #1, #669
+            666 => {
+                if modified && (mem.read(666) != 1105 || mem.read(667) != 1 || mem.read(668) != 669) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 669 } else { 669 };
+            }
+            // mul [64], #2, [64]
+            669 => {
+                if modified && (mem.read(669) != 1002 || mem.read(670) != 64 || mem.read(671) != 2 || mem.read(672) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 673;
+            }
+            // arb #-10
+            673 => {
+                if modified && (mem.read(673) != 109 || mem.read(674) != -10) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-10));
+                pc = 675;
+            }
+            // lt #26, rb-6, [63]
+            675 => {
+                if modified && (mem.read(675) != 2107 || mem.read(676) != 26 || mem.read(677) != -6 || mem.read(678) != 63) {
+                    break;
+                }
+                let a: i64 = 26;
+                let val = (a < get!(load(mem, get!(rb.checked_add(-6))))) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 679;
+            }
+            // jnz [63], #685
+            679 => {
+                if modified && (mem.read(679) != 1005 || mem.read(680) != 63 || mem.read(681) != 685) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 685 } else { 682 };
+            }
+            // jnz #1, #691
+            682 => {
+                if modified && (mem.read(682) != 1105 || mem.read(683) != 1 || mem.read(684) != 691) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 691 } else { 685 };
+            }
+            // out [675]
+            685 => {
+                if modified && (mem.read(685) != 4 || mem.read(686) != 675) {
+                    break;
+                }
+                write_output(get!(load(mem, 675)));
+                pc = 687;
+            }
+            // add [64], #1, [64]
+            687 => {
+                if modified && (mem.read(687) != 1001 || mem.read(688) != 64 || mem.read(689) != 1 || mem.read(690) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 691;
+            }
+            // mul [64], #2, [64]
+            691 => {
+                if modified && (mem.read(691) != 1002 || mem.read(692) != 64 || mem.read(693) != 2 || mem.read(694) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 695;
+            }
+            // arb #9
+            695 => {
+                if modified && (mem.read(695) != 109 || mem.read(696) != 9) {
+                    break;
+                }
+                rb = get!(rb.checked_add(9));
+                pc = 697;
+            }
+            // jnz rb+1, #703
+            697 => {
+                if modified && (mem.read(697) != 1205 || mem.read(698) != 1 || mem.read(699) != 703) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(1))));
+                pc = if a != 0 { 703 } else { 700 };
+            }
+            // jz #0, #709
+            700 => {
+                if modified && (mem.read(700) != 1106 || mem.read(701) != 0 || mem.read(702) != 709) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 709 } else { 703 };
+            }
+            // out [697]
+            703 => {
+                if modified && (mem.read(703) != 4 || mem.read(704) != 697) {
+                    break;
+                }
+                write_output(get!(load(mem, 697)));
+                pc = 705;
+            }
+            // add [64], #1, [64]
+            705 => {
+                if modified && (mem.read(705) != 1001 || mem.read(706) != 64 || mem.read(707) != 1 || mem.read(708) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 709;
+            }
+            // mul [64], #2, [64]
+            709 => {
+                if modified && (mem.read(709) != 1002 || mem.read(710) != 64 || mem.read(711) != 2 || mem.read(712) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 713;
+            }
+            // arb #-12
+            713 => {
+                if modified && (mem.read(713) != 109 || mem.read(714) != -12) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-12));
+                pc = 715;
+            }
+            // eq #22, rb-3, [63]
+            715 => {
+                if modified && (mem.read(715) != 2108 || mem.read(716) != 22 || mem.read(717) != -3 || mem.read(718) != 63) {
+                    break;
+                }
+                let a: i64 = 22;
+                let val = (a == get!(load(mem, get!(rb.checked_add(-3))))) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 719;
+            }
+            // jnz [63], #729
+            719 => {
+                if modified && (mem.read(719) != 1005 || mem.read(720) != 63 || mem.read(721) != 729) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 729 } else { 722 };
+            }
+            // add [64], #1, [64]
+            722 => {
+                if modified && (mem.read(722) != 1001 || mem.read(723) != 64 || mem.read(724) != 1 || mem.read(725) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 726;
+            }
+            // jz #0, #731
+            726 => {
+                if modified && (mem.read(726) != 1106 || mem.read(727) != 0 || mem.read(728) != 731) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 731 } else { 729 };
+            }
+            // out [715]
+            729 => {
+                if modified && (mem.read(729) != 4 || mem.read(730) != 715) {
+                    break;
+                }
+                write_output(get!(load(mem, 715)));
+                pc = 731;
+            }
+            // mul [64], #2, [64]
+            731 => {
+                if modified && (mem.read(731) != 1002 || mem.read(732) != 64 || mem.read(733) != 2 || mem.read(734) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 735;
+            }
+            // arb #-11
+            735 => {
+                if modified && (mem.read(735) != 109 || mem.read(736) != -11) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-11));
+                pc = 737;
+            }
+            // lt rb+10, #35, [63]
+            737 => {
+                if modified && (mem.read(737) != 1207 || mem.read(738) != 10 || mem.read(739) != 35 || mem.read(740) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(10))));
+                let val = (a < 35) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 741;
+            }
+            // jnz [63], #753
+            741 => {
+                if modified && (mem.read(741) != 1005 || mem.read(742) != 63 || mem.read(743) != 753) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 753 } else { 744 };
+            }
+            // out [737]
+            744 => {
+                if modified && (mem.read(744) != 4 || mem.read(745) != 737) {
+                    break;
+                }
+                write_output(get!(load(mem, 737)));
+                pc = 746;
+            }
+            // add [64], #1, [64]
+            746 => {
+                if modified && (mem.read(746) != 1001 || mem.read(747) != 64 || mem.read(748) != 1 || mem.read(749) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 750;
+            }
+            // jz #0, #753
+            750 => {
+                if modified && (mem.read(750) != 1106 || mem.read(751) != 0 || mem.read(752) != 753) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 753 } else { 753 };
+            }
+            // mul [64], #2, [64]
+            753 => {
+                if modified && (mem.read(753) != 1002 || mem.read(754) != 64 || mem.read(755) != 2 || mem.read(756) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 757;
+            }
+            // arb #9
+            757 => {
+                if modified && (mem.read(757) != 109 || mem.read(758) != 9) {
+                    break;
+                }
+                rb = get!(rb.checked_add(9));
+                pc = 759;
+            }
+            // add #46, #0, rb+5
+            759 => {
+                if modified && (mem.read(759) != 21101 || mem.read(760) != 46 || mem.read(761) != 0 || mem.read(762) != 5) {
+                    break;
+                }
+                let a: i64 = 46;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, get!(rb.checked_add(5)), val, &mut modified));
+                pc = 763;
+            }
+            // eq [1010], #43, [63]
+            763 => {
+                if modified && (mem.read(763) != 1008 || mem.read(764) != 1010 || mem.read(765) != 43 || mem.read(766) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1010));
+                let val = (a == 43) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 767;
+            }
+            // jnz [63], #773
+            767 => {
+                if modified && (mem.read(767) != 1005 || mem.read(768) != 63 || mem.read(769) != 773) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 773 } else { 770 };
+            }
+            // jnz #1, #779
+            770 => {
+                if modified && (mem.read(770) != 1105 || mem.read(771) != 1 || mem.read(772) != 779) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 779 } else { 773 };
+            }
+            // out [759]
+            773 => {
+                if modified && (mem.read(773) != 4 || mem.read(774) != 759) {
+                    break;
+                }
+                write_output(get!(load(mem, 759)));
+                pc = 775;
+            }
+            // add [64], #1, [64]
+            775 => {
+                if modified && (mem.read(775) != 1001 || mem.read(776) != 64 || mem.read(777) != 1 || mem.read(778) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 779;
+            }
+            // mul [64], #2, [64]
+            779 => {
+                if modified && (mem.read(779) != 1002 || mem.read(780) != 64 || mem.read(781) != 2 || mem.read(782) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 783;
+            }
+            // arb #-1
+            783 => {
+                if modified && (mem.read(783) != 109 || mem.read(784) != -1) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-1));
+                pc = 785;
+            }
+            // add rb+4, #0, [63]
+            785 => {
+                if modified && (mem.read(785) != 1201 || mem.read(786) != 4 || mem.read(787) != 0 || mem.read(788) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(4))));
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 789;
+            }
+            // eq [63], #26, [63]
+            789 => {
+                if modified && (mem.read(789) != 1008 || mem.read(790) != 63 || mem.read(791) != 26 || mem.read(792) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 26) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 793;
+            }
+            // jnz [63], #801
+            793 => {
+                if modified && (mem.read(793) != 1005 || mem.read(794) != 63 || mem.read(795) != 801) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 801 } else { 796 };
+            }
+            // out [785]
+            796 => {
+                if modified && (mem.read(796) != 4 || mem.read(797) != 785) {
+                    break;
+                }
+                write_output(get!(load(mem, 785)));
+                pc = 798;
+            }
+            // jnz #1, #805
+            798 => {
+                if modified && (mem.read(798) != 1105 || mem.read(799) != 1 || mem.read(800) != 805) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 805 } else { 801 };
+            }
+            // add [64], #1, [64]
+            801 => {
+                if modified && (mem.read(801) != 1001 || mem.read(802) != 64 || mem.read(803) != 1 || mem.read(804) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 805;
+            }
+            // mul [64], #2, [64]
+            805 => {
+                if modified && (mem.read(805) != 1002 || mem.read(806) != 64 || mem.read(807) != 2 || mem.read(808) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 809;
+            }
+            // arb #7
+            809 => {
+                if modified && (mem.read(809) != 109 || mem.read(810) != 7) {
+                    break;
+                }
+                rb = get!(rb.checked_add(7));
+                pc = 811;
+            }
+            // add rb-8, #0, [63]
+            811 => {
+                if modified && (mem.read(811) != 1201 || mem.read(812) != -8 || mem.read(813) != 0 || mem.read(814) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-8))));
+                let val = get!(a.checked_add(0));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 815;
+            }
+            // eq [63], #22, [63]
+            815 => {
+                if modified && (mem.read(815) != 1008 || mem.read(816) != 63 || mem.read(817) != 22 || mem.read(818) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 22) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 819;
+            }
+            // jnz [63], #825
+            819 => {
+                if modified && (mem.read(819) != 1005 || mem.read(820) != 63 || mem.read(821) != 825) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 825 } else { 822 };
+            }
+            // jz #0, #831
+            822 => {
+                if modified && (mem.read(822) != 1106 || mem.read(823) != 0 || mem.read(824) != 831) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 831 } else { 825 };
+            }
+            // out [811]
+            825 => {
+                if modified && (mem.read(825) != 4 || mem.read(826) != 811) {
+                    break;
+                }
+                write_output(get!(load(mem, 811)));
+                pc = 827;
+            }
+            // add [64], #1, [64]
+            827 => {
+                if modified && (mem.read(827) != 1001 || mem.read(828) != 64 || mem.read(829) != 1 || mem.read(830) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 831;
+            }
+            // mul [64], #2, [64]
+            831 => {
+                if modified && (mem.read(831) != 1002 || mem.read(832) != 64 || mem.read(833) != 2 || mem.read(834) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 835;
+            }
+            // arb #-1
+            835 => {
+                if modified && (mem.read(835) != 109 || mem.read(836) != -1) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-1));
+                pc = 837;
+            }
+            // mul rb-6, #1, [63]
+            837 => {
+                if modified && (mem.read(837) != 1202 || mem.read(838) != -6 || mem.read(839) != 1 || mem.read(840) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-6))));
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 841;
+            }
+            // eq [63], #23, [63]
+            841 => {
+                if modified && (mem.read(841) != 1008 || mem.read(842) != 63 || mem.read(843) != 23 || mem.read(844) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                let val = (a == 23) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 845;
+            }
+            // jnz [63], #855
+            845 => {
+                if modified && (mem.read(845) != 1005 || mem.read(846) != 63 || mem.read(847) != 855) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 855 } else { 848 };
+            }
+            // add [64], #1, [64]
+            848 => {
+                if modified && (mem.read(848) != 1001 || mem.read(849) != 64 || mem.read(850) != 1 || mem.read(851) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 852;
+            }
+            // jz #0, #857
+            852 => {
+                if modified && (mem.read(852) != 1106 || mem.read(853) != 0 || mem.read(854) != 857) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 857 } else { 855 };
+            }
+            // out [837]
+            855 => {
+                if modified && (mem.read(855) != 4 || mem.read(856) != 837) {
+                    break;
+                }
+                write_output(get!(load(mem, 837)));
+                pc = 857;
+            }
+            // mul [64], #2, [64]
+            857 => {
+                if modified && (mem.read(857) != 1002 || mem.read(858) != 64 || mem.read(859) != 2 || mem.read(860) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 861;
+            }
+            // arb #7
+            861 => {
+                if modified && (mem.read(861) != 109 || mem.read(862) != 7) {
+                    break;
+                }
+                rb = get!(rb.checked_add(7));
+                pc = 863;
+            }
+            // mul #47, #1, rb+0
+            863 => {
+                if modified && (mem.read(863) != 21102 || mem.read(864) != 47 || mem.read(865) != 1 || mem.read(866) != 0) {
+                    break;
+                }
+                let a: i64 = 47;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, get!(rb.checked_add(0)), val, &mut modified));
+                pc = 867;
+            }
+            // eq [1017], #47, [63]
+            867 => {
+                if modified && (mem.read(867) != 1008 || mem.read(868) != 1017 || mem.read(869) != 47 || mem.read(870) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1017));
+                let val = (a == 47) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 871;
+            }
+            // jnz [63], #883
+            871 => {
+                if modified && (mem.read(871) != 1005 || mem.read(872) != 63 || mem.read(873) != 883) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 883 } else { 874 };
+            }
+            // out [863]
+            874 => {
+                if modified && (mem.read(874) != 4 || mem.read(875) != 863) {
+                    break;
+                }
+                write_output(get!(load(mem, 863)));
+                pc = 876;
+            }
+            // add [64], #1, [64]
+            876 => {
+                if modified && (mem.read(876) != 1001 || mem.read(877) != 64 || mem.read(878) != 1 || mem.read(879) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 880;
+            }
+            // jz #0, #883
+            880 => {
+                if modified && (mem.read(880) != 1106 || mem.read(881) != 0 || mem.read(882) != 883) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 883 } else { 883 };
+            }
+            // mul [64], #2, [64]
+            883 => {
+                if modified && (mem.read(883) != 1002 || mem.read(884) != 64 || mem.read(885) != 2 || mem.read(886) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_mul(2));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 887;
+            }
+            // arb #8
+            887 => {
+                if modified && (mem.read(887) != 109 || mem.read(888) != 8) {
+                    break;
+                }
+                rb = get!(rb.checked_add(8));
+                pc = 889;
+            }
+            // jnz #1, rb-2
+            889 => {
+                if modified && (mem.read(889) != 2105 || mem.read(890) != 1 || mem.read(891) != -2) {
+                    break;
+                }
+                let a: i64 = 1;
+                let b: i64 = get!(load(mem, get!(rb.checked_add(-2))));
+                pc = if a != 0 { get!(jump(b)) } else { 892 };
+            }
+            // jz #0, #901
+            892 => {
+                if modified && (mem.read(892) != 1106 || mem.read(893) != 0 || mem.read(894) != 901) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 901 } else { 895 };
+            }
+            // out [889]
+            895 => {
+                if modified && (mem.read(895) != 4 || mem.read(896) != 889) {
+                    break;
+                }
+                write_output(get!(load(mem, 889)));
+                pc = 897;
+            }
+            // add [64], #1, [64]
+            897 => {
+                if modified && (mem.read(897) != 1001 || mem.read(898) != 64 || mem.read(899) != 1 || mem.read(900) != 64) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 64));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 64, val, &mut modified));
+                pc = 901;
+            }
+            // out [64]
+            901 => {
+                if modified && (mem.read(901) != 4 || mem.read(902) != 64) {
+                    break;
+                }
+                write_output(get!(load(mem, 64)));
+                pc = 903;
+            }
+            // hlt
+            903 => {
+                if modified && (mem.read(903) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+            // add #0, #27, rb+1
+            904 => {
+                if modified && (mem.read(904) != 21101 || mem.read(905) != 0 || mem.read(906) != 27 || mem.read(907) != 1) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(27));
+                get!(store(mem, get!(rb.checked_add(1)), val, &mut modified));
+                pc = 908;
+            }
+            // add #915, #0, rb+0
+            908 => {
+                if modified && (mem.read(908) != 21101 || mem.read(909) != 915 || mem.read(910) != 0 || mem.read(911) != 0) {
+                    break;
+                }
+                let a: i64 = 915;
+                let val = get!(a.checked_add(0));
+                get!(store(mem, get!(rb.checked_add(0)), val, &mut modified));
+                pc = 912;
+            }
+            // jnz #1, #922
+            912 => {
+                if modified && (mem.read(912) != 1105 || mem.read(913) != 1 || mem.read(914) != 922) {
+                    break;
+                }
+                let a: i64 = 1;
+                pc = if a != 0 { 922 } else { 915 };
+            }
+            // add rb+1, #20897, rb+1
+            915 => {
+                if modified && (mem.read(915) != 21201 || mem.read(916) != 1 || mem.read(917) != 20897 || mem.read(918) != 1) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(1))));
+                let val = get!(a.checked_add(20897));
+                get!(store(mem, get!(rb.checked_add(1)), val, &mut modified));
+                pc = 919;
+            }
+            // out rb+1
+            919 => {
+                if modified && (mem.read(919) != 204 || mem.read(920) != 1) {
+                    break;
+                }
+                write_output(get!(load(mem, get!(rb.checked_add(1)))));
+                pc = 921;
+            }
+            // hlt
+            921 => {
+                if modified && (mem.read(921) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+            // arb #3
+            922 => {
+                if modified && (mem.read(922) != 109 || mem.read(923) != 3) {
+                    break;
+                }
+                rb = get!(rb.checked_add(3));
+                pc = 924;
+            }
+            // lt rb-2, #3, [63]
+            924 => {
+                if modified && (mem.read(924) != 1207 || mem.read(925) != -2 || mem.read(926) != 3 || mem.read(927) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-2))));
+                let val = (a < 3) as i64;
+                get!(store(mem, 63, val, &mut modified));
+                pc = 928;
+            }
+            // jnz [63], #964
+            928 => {
+                if modified && (mem.read(928) != 1005 || mem.read(929) != 63 || mem.read(930) != 964) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 63));
+                pc = if a != 0 { 964 } else { 931 };
+            }
+            // add rb-2, #-1, rb+1
+            931 => {
+                if modified && (mem.read(931) != 21201 || mem.read(932) != -2 || mem.read(933) != -1 || mem.read(934) != 1) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-2))));
+                let val = get!(a.checked_add(-1));
+                get!(store(mem, get!(rb.checked_add(1)), val, &mut modified));
+                pc = 935;
+            }
+            // add #0, #942, rb+0
+            935 => {
+                if modified && (mem.read(935) != 21101 || mem.read(936) != 0 || mem.read(937) != 942 || mem.read(938) != 0) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(942));
+                get!(store(mem, get!(rb.checked_add(0)), val, &mut modified));
+                pc = 939;
+            }
+            // jz #0, #922
+            939 => {
+                if modified && (mem.read(939) != 1106 || mem.read(940) != 0 || mem.read(941) != 922) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 922 } else { 942 };
+            }
+            // add #0, rb+1, rb-1
+            942 => {
+                if modified && (mem.read(942) != 22101 || mem.read(943) != 0 || mem.read(944) != 1 || mem.read(945) != -1) {
+                    break;
+                }
+                let a: i64 = 0;
+                let val = get!(a.checked_add(get!(load(mem, get!(rb.checked_add(1))))));
+                get!(store(mem, get!(rb.checked_add(-1)), val, &mut modified));
+                pc = 946;
+            }
+            // add rb-2, #-3, rb+1
+            946 => {
+                if modified && (mem.read(946) != 21201 || mem.read(947) != -2 || mem.read(948) != -3 || mem.read(949) != 1) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(-2))));
+                let val = get!(a.checked_add(-3));
+                get!(store(mem, get!(rb.checked_add(1)), val, &mut modified));
+                pc = 950;
+            }
+            // mul #957, #1, rb+0
+            950 => {
+                if modified && (mem.read(950) != 21102 || mem.read(951) != 957 || mem.read(952) != 1 || mem.read(953) != 0) {
+                    break;
+                }
+                let a: i64 = 957;
+                let val = get!(a.checked_mul(1));
+                get!(store(mem, get!(rb.checked_add(0)), val, &mut modified));
+                pc = 954;
+            }
+            // jz #0, #922
+            954 => {
+                if modified && (mem.read(954) != 1106 || mem.read(955) != 0 || mem.read(956) != 922) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 922 } else { 957 };
+            }
+            // add rb+1, rb-1, rb-2
+            957 => {
+                if modified && (mem.read(957) != 22201 || mem.read(958) != 1 || mem.read(959) != -1 || mem.read(960) != -2) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, get!(rb.checked_add(1))));
+                let val = get!(a.checked_add(get!(load(mem, get!(rb.checked_add(-1))))));
+                get!(store(mem, get!(rb.checked_add(-2)), val, &mut modified));
+                pc = 961;
+            }
+            // jz #0, #968
+            961 => {
+                if modified && (mem.read(961) != 1106 || mem.read(962) != 0 || mem.read(963) != 968) {
+                    break;
+                }
+                let a: i64 = 0;
+                pc = if a == 0 { 968 } else { 964 };
+            }
+            // mul #1, rb-2, rb-2
+            964 => {
+                if modified && (mem.read(964) != 22102 || mem.read(965) != 1 || mem.read(966) != -2 || mem.read(967) != -2) {
+                    break;
+                }
+                let a: i64 = 1;
+                let val = get!(a.checked_mul(get!(load(mem, get!(rb.checked_add(-2))))));
+                get!(store(mem, get!(rb.checked_add(-2)), val, &mut modified));
+                pc = 968;
+            }
+            // arb #-3
+            968 => {
+                if modified && (mem.read(968) != 109 || mem.read(969) != -3) {
+                    break;
+                }
+                rb = get!(rb.checked_add(-3));
+                pc = 970;
+            }
+            // jnz #1, rb+0
+            970 => {
+                if modified && (mem.read(970) != 2105 || mem.read(971) != 1 || mem.read(972) != 0) {
+                    break;
+                }
+                let a: i64 = 1;
+                let b: i64 = get!(load(mem, get!(rb.checked_add(0))));
+                pc = if a != 0 { get!(jump(b)) } else { 973 };
+            }
+_ => break,
+        }
+    }
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    machine.pos = pc;
+    machine.relbase = rb;
+    let result = machine.run_with(read_input, write_output);
+    *mem = machine.mem;
+    result
+}
@@ -0,0 +1,375 @@
+// Generated by intcode::compile; do not edit.
+
+#![allow(unused_mut, unused_variables, unused_macros, dead_code, clippy::all)]
+
+use crate::intcode::{IntcodeError, Machine, Memory, DENSE_MAX_ADDR};
+
+// Leave the compiled code, so the interpreter runs the current instruction.
+macro_rules! get {
+    ($e:expr) => {
+        match $e {
+            Some(val) => val,
+            None => break,
+        }
+    };
+}
+
+fn load(mem: &Vec<i64>, addr: i64) -> Option<i64> {
+    if addr < 0 { None } else { Some(mem.read(addr as usize)) }
+}
+
+fn dest(addr: i64) -> Option<usize> {
+    if addr < 0 || addr as u64 > DENSE_MAX_ADDR as u64 { None } else { Some(addr as usize) }
+}
+
+fn store(mem: &mut Vec<i64>, addr: i64, val: i64, modified: &mut bool) -> Option<()> {
+    let addr = dest(addr)?;
+    mem.write(addr, val).ok()?;
+    if addr < IMAGE.len() && CODE[addr] != 0 && val != IMAGE[addr] {
+        *modified = true;
+    }
+    Some(())
+}
+
+fn jump(target: i64) -> Option<usize> {
+    if target < 0 { None } else { Some(target as usize) }
+}
+
+const IMAGE: [i64; 121] = [
+    1, 0, 0, 3, 1, 1, 2, 3, 1, 3, 4, 3, 1, 5, 0, 3,
+    2, 1, 10, 19, 1, 6, 19, 23, 2, 23, 6, 27, 2, 6, 27, 31,
+    2, 13, 31, 35, 1, 10, 35, 39, 2, 39, 13, 43, 1, 43, 13, 47,
+    1, 6, 47, 51, 1, 10, 51, 55, 2, 55, 6, 59, 1, 5, 59, 63,
+    2, 9, 63, 67, 1, 6, 67, 71, 2, 9, 71, 75, 1, 6, 75, 79,
+    2, 79, 13, 83, 1, 83, 10, 87, 1, 13, 87, 91, 1, 91, 10, 95,
+    2, 9, 95, 99, 1, 5, 99, 103, 2, 10, 103, 107, 1, 107, 2, 111,
+    1, 111, 5, 0, 99, 2, 14, 0, 0,
+];
+
+const CODE: [u8; 121] = [
+    1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 0, 0, 0, 0,
+];
+
+pub fn interpret(mem: &mut Vec<i64>,
+                 read_input: &mut dyn FnMut() -> i64,
+                 write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let mut pc = 0usize;
+    let mut rb = 0i64;
+    let mut modified = (0..IMAGE.len())
+        .any(|addr| CODE[addr] != 0 && mem.read(addr) != IMAGE[addr]);
+    loop {
+        match pc {
+            // add [0], [0], [3]
+            0 => {
+                if modified && (mem.read(0) != 1 || mem.read(3) != 3) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, mem.read(1)));
+                let val = get!(a.checked_add(get!(load(mem, mem.read(2)))));
+                get!(store(mem, 3, val, &mut modified));
+                pc = 4;
+            }
+            // add [1], [2], [3]
+            4 => {
+                if modified && (mem.read(4) != 1 || mem.read(5) != 1 || mem.read(6) != 2 || mem.read(7) != 3) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1));
+                let val = get!(a.checked_add(get!(load(mem, 2))));
+                get!(store(mem, 3, val, &mut modified));
+                pc = 8;
+            }
+            // add [3], [4], [3]
+            8 => {
+                if modified && (mem.read(8) != 1 || mem.read(9) != 3 || mem.read(10) != 4 || mem.read(11) != 3) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 3));
+                let val = get!(a.checked_add(get!(load(mem, 4))));
+                get!(store(mem, 3, val, &mut modified));
+                pc = 12;
+            }
+            // add [5], [0], [3]
+            12 => {
+                if modified && (mem.read(12) != 1 || mem.read(13) != 5 || mem.read(14) != 0 || mem.read(15) != 3) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 5));
+                let val = get!(a.checked_add(get!(load(mem, 0))));
+                get!(store(mem, 3, val, &mut modified));
+                pc = 16;
+            }
+            // mul [1], [10], [19]
+            16 => {
+                if modified && (mem.read(16) != 2 || mem.read(17) != 1 || mem.read(18) != 10 || mem.read(19) != 19) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 1));
+                let val = get!(a.checked_mul(get!(load(mem, 10))));
+                get!(store(mem, 19, val, &mut modified));
+                pc = 20;
+            }
+            // add [6], [19], [23]
+            20 => {
+                if modified && (mem.read(20) != 1 || mem.read(21) != 6 || mem.read(22) != 19 || mem.read(23) != 23) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 6));
+                let val = get!(a.checked_add(get!(load(mem, 19))));
+                get!(store(mem, 23, val, &mut modified));
+                pc = 24;
+            }
+            // mul [23], [6], [27]
+            24 => {
+                if modified && (mem.read(24) != 2 || mem.read(25) != 23 || mem.read(26) != 6 || mem.read(27) != 27) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 23));
+                let val = get!(a.checked_mul(get!(load(mem, 6))));
+                get!(store(mem, 27, val, &mut modified));
+                pc = 28;
+            }
+            // mul [6], [27], [31]
+            28 => {
+                if modified && (mem.read(28) != 2 || mem.read(29) != 6 || mem.read(30) != 27 || mem.read(31) != 31) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 6));
+                let val = get!(a.checked_mul(get!(load(mem, 27))));
+                get!(store(mem, 31, val, &mut modified));
+                pc = 32;
+            }
+            // mul [13], [31], [35]
+            32 => {
+                if modified && (mem.read(32) != 2 || mem.read(33) != 13 || mem.read(34) != 31 || mem.read(35) != 35) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 13));
+                let val = get!(a.checked_mul(get!(load(mem, 31))));
+                get!(store(mem, 35, val, &mut modified));
+                pc = 36;
+            }
+            // add [10], [35], [39]
+            36 => {
+                if modified && (mem.read(36) != 1 || mem.read(37) != 10 || mem.read(38) != 35 || mem.read(39) != 39) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 10));
+                let val = get!(a.checked_add(get!(load(mem, 35))));
+                get!(store(mem, 39, val, &mut modified));
+                pc = 40;
+            }
+            // mul [39], [13], [43]
+            40 => {
+                if modified && (mem.read(40) != 2 || mem.read(41) != 39 || mem.read(42) != 13 || mem.read(43) != 43) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 39));
+                let val = get!(a.checked_mul(get!(load(mem, 13))));
+                get!(store(mem, 43, val, &mut modified));
+                pc = 44;
+            }
+            // add [43], [13], [47]
+            44 => {
+                if modified && (mem.read(44) != 1 || mem.read(45) != 43 || mem.read(46) != 13 || mem.read(47) != 47) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 43));
+                let val = get!(a.checked_add(get!(load(mem, 13))));
+                get!(store(mem, 47, val, &mut modified));
+                pc = 48;
+            }
+            // add [6], [47], [51]
+            48 => {
+                if modified && (mem.read(48) != 1 || mem.read(49) != 6 || mem.read(50) != 47 || mem.read(51) != 51) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 6));
+                let val = get!(a.checked_add(get!(load(mem, 47))));
+                get!(store(mem, 51, val, &mut modified));
+                pc = 52;
+            }
+            // add [10], [51], [55]
+            52 => {
+                if modified && (mem.read(52) != 1 || mem.read(53) != 10 || mem.read(54) != 51 || mem.read(55) != 55) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 10));
+                let val = get!(a.checked_add(get!(load(mem, 51))));
+                get!(store(mem, 55, val, &mut modified));
+                pc = 56;
+            }
+            // mul [55], [6], [59]
+            56 => {
+                if modified && (mem.read(56) != 2 || mem.read(57) != 55 || mem.read(58) != 6 || mem.read(59) != 59) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 55));
+                let val = get!(a.checked_mul(get!(load(mem, 6))));
+                get!(store(mem, 59, val, &mut modified));
+                pc = 60;
+            }
+            // add [5], [59], [63]
+            60 => {
+                if modified && (mem.read(60) != 1 || mem.read(61) != 5 || mem.read(62) != 59 || mem.read(63) != 63) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 5));
+                let val = get!(a.checked_add(get!(load(mem, 59))));
+                get!(store(mem, 63, val, &mut modified));
+                pc = 64;
+            }
+            // mul [9], [63], [67]
+            64 => {
+                if modified && (mem.read(64) != 2 || mem.read(65) != 9 || mem.read(66) != 63 || mem.read(67) != 67) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 9));
+                let val = get!(a.checked_mul(get!(load(mem, 63))));
+                get!(store(mem, 67, val, &mut modified));
+                pc = 68;
+            }
+            // add [6], [67], [71]
+            68 => {
+                if modified && (mem.read(68) != 1 || mem.read(69) != 6 || mem.read(70) != 67 || mem.read(71) != 71) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 6));
+                let val = get!(a.checked_add(get!(load(mem, 67))));
+                get!(store(mem, 71, val, &mut modified));
+                pc = 72;
+            }
+            // mul [9], [71], [75]
+            72 => {
+                if modified && (mem.read(72) != 2 || mem.read(73) != 9 || mem.read(74) != 71 || mem.read(75) != 75) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 9));
+                let val = get!(a.checked_mul(get!(load(mem, 71))));
+                get!(store(mem, 75, val, &mut modified));
+                pc = 76;
+            }
+            // add [6], [75], [79]
+            76 => {
+                if modified && (mem.read(76) != 1 || mem.read(77) != 6 || mem.read(78) != 75 || mem.read(79) != 79) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 6));
+                let val = get!(a.checked_add(get!(load(mem, 75))));
+                get!(store(mem, 79, val, &mut modified));
+                pc = 80;
+            }
+            // mul [79], [13], [83]
+            80 => {
+                if modified && (mem.read(80) != 2 || mem.read(81) != 79 || mem.read(82) != 13 || mem.read(83) != 83) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 79));
+                let val = get!(a.checked_mul(get!(load(mem, 13))));
+                get!(store(mem, 83, val, &mut modified));
+                pc = 84;
+            }
+            // add [83], [10], [87]
+            84 => {
+                if modified && (mem.read(84) != 1 || mem.read(85) != 83 || mem.read(86) != 10 || mem.read(87) != 87) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 83));
+                let val = get!(a.checked_add(get!(load(mem, 10))));
+                get!(store(mem, 87, val, &mut modified));
+                pc = 88;
+            }
+            // add [13], [87], [91]
+            88 => {
+                if modified && (mem.read(88) != 1 || mem.read(89) != 13 || mem.read(90) != 87 || mem.read(91) != 91) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 13));
+                let val = get!(a.checked_add(get!(load(mem, 87))));
+                get!(store(mem, 91, val, &mut modified));
+                pc = 92;
+            }
+            // add [91], [10], [95]
+            92 => {
+                if modified && (mem.read(92) != 1 || mem.read(93) != 91 || mem.read(94) != 10 || mem.read(95) != 95) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 91));
+                let val = get!(a.checked_add(get!(load(mem, 10))));
+                get!(store(mem, 95, val, &mut modified));
+                pc = 96;
+            }
+            // mul [9], [95], [99]
+            96 => {
+                if modified && (mem.read(96) != 2 || mem.read(97) != 9 || mem.read(98) != 95 || mem.read(99) != 99) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 9));
+                let val = get!(a.checked_mul(get!(load(mem, 95))));
+                get!(store(mem, 99, val, &mut modified));
+                pc = 100;
+            }
+            // add [5], [99], [103]
+            100 => {
+                if modified && (mem.read(100) != 1 || mem.read(101) != 5 || mem.read(102) != 99 || mem.read(103) != 103) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 5));
+                let val = get!(a.checked_add(get!(load(mem, 99))));
+                get!(store(mem, 103, val, &mut modified));
+                pc = 104;
+            }
+            // mul [10], [103], [107]
+            104 => {
+                if modified && (mem.read(104) != 2 || mem.read(105) != 10 || mem.read(106) != 103 || mem.read(107) != 107) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 10));
+                let val = get!(a.checked_mul(get!(load(mem, 103))));
+                get!(store(mem, 107, val, &mut modified));
+                pc = 108;
+            }
+            // add [107], [2], [111]
+            108 => {
+                if modified && (mem.read(108) != 1 || mem.read(109) != 107 || mem.read(110) != 2 || mem.read(111) != 111) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 107));
+                let val = get!(a.checked_add(get!(load(mem, 2))));
+                get!(store(mem, 111, val, &mut modified));
+                pc = 112;
+            }
+            // add [111], [5], [0]
+            112 => {
+                if modified && (mem.read(112) != 1 || mem.read(113) != 111 || mem.read(114) != 5 || mem.read(115) != 0) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 111));
+                let val = get!(a.checked_add(get!(load(mem, 5))));
+                get!(store(mem, 0, val, &mut modified));
+                pc = 116;
+            }
+            // hlt
+            116 => {
+                if modified && (mem.read(116) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+_ => break,
+        }
+    }
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    machine.pos = pc;
+    machine.relbase = rb;
+    let result = machine.run_with(read_input, write_output);
+    *mem = machine.mem;
+    result
+}
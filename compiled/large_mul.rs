@@ -0,0 +1,89 @@
+// Generated by intcode::compile; do not edit.
+
+#![allow(unused_mut, unused_variables, unused_macros, dead_code, clippy::all)]
+
+use crate::intcode::{IntcodeError, Machine, Memory, DENSE_MAX_ADDR};
+
+// Leave the compiled code, so the interpreter runs the current instruction.
+macro_rules! get {
+    ($e:expr) => {
+        match $e {
+            Some(val) => val,
+            None => break,
+        }
+    };
+}
+
+fn load(mem: &Vec<i64>, addr: i64) -> Option<i64> {
+    if addr < 0 { None } else { Some(mem.read(addr as usize)) }
+}
+
+fn dest(addr: i64) -> Option<usize> {
+    if addr < 0 || addr as u64 > DENSE_MAX_ADDR as u64 { None } else { Some(addr as usize) }
+}
+
+fn store(mem: &mut Vec<i64>, addr: i64, val: i64, modified: &mut bool) -> Option<()> {
+    let addr = dest(addr)?;
+    mem.write(addr, val).ok()?;
+    if addr < IMAGE.len() && CODE[addr] != 0 && val != IMAGE[addr] {
+        *modified = true;
+    }
+    Some(())
+}
+
+fn jump(target: i64) -> Option<usize> {
+    if target < 0 { None } else { Some(target as usize) }
+}
+
+const IMAGE: [i64; 8] = [
+    1102, 34915192, 34915192, 7, 4, 7, 99, 0,
+];
+
+const CODE: [u8; 8] = [
+    1, 1, 1, 1, 1, 1, 1, 0,
+];
+
+pub fn interpret(mem: &mut Vec<i64>,
+                 read_input: &mut dyn FnMut() -> i64,
+                 write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let mut pc = 0usize;
+    let mut rb = 0i64;
+    let mut modified = (0..IMAGE.len())
+        .any(|addr| CODE[addr] != 0 && mem.read(addr) != IMAGE[addr]);
+    loop {
+        match pc {
+            // mul #34915192, #34915192, [7]
+            0 => {
+                if modified && (mem.read(0) != 1102 || mem.read(1) != 34915192 || mem.read(2) != 34915192 || mem.read(3) != 7) {
+                    break;
+                }
+                let a: i64 = 34915192;
+                let val = get!(a.checked_mul(34915192));
+                get!(store(mem, 7, val, &mut modified));
+                pc = 4;
+            }
+            // out [7]
+            4 => {
+                if modified && (mem.read(4) != 4 || mem.read(5) != 7) {
+                    break;
+                }
+                write_output(get!(load(mem, 7)));
+                pc = 6;
+            }
+            // hlt
+            6 => {
+                if modified && (mem.read(6) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+_ => break,
+        }
+    }
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    machine.pos = pc;
+    machine.relbase = rb;
+    let result = machine.run_with(read_input, write_output);
+    *mem = machine.mem;
+    result
+}
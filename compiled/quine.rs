@@ -0,0 +1,115 @@
+// Generated by intcode::compile; do not edit.
+
+#![allow(unused_mut, unused_variables, unused_macros, dead_code, clippy::all)]
+
+use crate::intcode::{IntcodeError, Machine, Memory, DENSE_MAX_ADDR};
+
+// Leave the compiled code, so the interpreter runs the current instruction.
+macro_rules! get {
+    ($e:expr) => {
+        match $e {
+            Some(val) => val,
+            None => break,
+        }
+    };
+}
+
+fn load(mem: &Vec<i64>, addr: i64) -> Option<i64> {
+    if addr < 0 { None } else { Some(mem.read(addr as usize)) }
+}
+
+fn dest(addr: i64) -> Option<usize> {
+    if addr < 0 || addr as u64 > DENSE_MAX_ADDR as u64 { None } else { Some(addr as usize) }
+}
+
+fn store(mem: &mut Vec<i64>, addr: i64, val: i64, modified: &mut bool) -> Option<()> {
+    let addr = dest(addr)?;
+    mem.write(addr, val).ok()?;
+    if addr < IMAGE.len() && CODE[addr] != 0 && val != IMAGE[addr] {
+        *modified = true;
+    }
+    Some(())
+}
+
+fn jump(target: i64) -> Option<usize> {
+    if target < 0 { None } else { Some(target as usize) }
+}
+
+const IMAGE: [i64; 16] = [
+    109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+];
+
+const CODE: [u8; 16] = [
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+];
+
+pub fn interpret(mem: &mut Vec<i64>,
+                 read_input: &mut dyn FnMut() -> i64,
+                 write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let mut pc = 0usize;
+    let mut rb = 0i64;
+    let mut modified = (0..IMAGE.len())
+        .any(|addr| CODE[addr] != 0 && mem.read(addr) != IMAGE[addr]);
+    loop {
+        match pc {
+            // arb #1
+            0 => {
+                if modified && (mem.read(0) != 109 || mem.read(1) != 1) {
+                    break;
+                }
+                rb = get!(rb.checked_add(1));
+                pc = 2;
+            }
+            // out rb-1
+            2 => {
+                if modified && (mem.read(2) != 204 || mem.read(3) != -1) {
+                    break;
+                }
+                write_output(get!(load(mem, get!(rb.checked_add(-1)))));
+                pc = 4;
+            }
+            // add [100], #1, [100]
+            4 => {
+                if modified && (mem.read(4) != 1001 || mem.read(5) != 100 || mem.read(6) != 1 || mem.read(7) != 100) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 100));
+                let val = get!(a.checked_add(1));
+                get!(store(mem, 100, val, &mut modified));
+                pc = 8;
+            }
+            // eq [100], #16, [101]
+            8 => {
+                if modified && (mem.read(8) != 1008 || mem.read(9) != 100 || mem.read(10) != 16 || mem.read(11) != 101) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 100));
+                let val = (a == 16) as i64;
+                get!(store(mem, 101, val, &mut modified));
+                pc = 12;
+            }
+            // jz [101], #0
+            12 => {
+                if modified && (mem.read(12) != 1006 || mem.read(13) != 101 || mem.read(14) != 0) {
+                    break;
+                }
+                let a: i64 = get!(load(mem, 101));
+                pc = if a == 0 { 0 } else { 15 };
+            }
+            // hlt
+            15 => {
+                if modified && (mem.read(15) != 99) {
+                    break;
+                }
+                return Ok(());
+            }
+_ => break,
+        }
+    }
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    machine.pos = pc;
+    machine.relbase = rb;
+    let result = machine.run_with(read_input, write_output);
+    *mem = machine.mem;
+    result
+}
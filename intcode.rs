@@ -0,0 +1,716 @@
+// Intcode virtual machine shared by the Intcode days.  Include it from a
+// day's source with `mod intcode;`.
+
+#![allow(dead_code)]
+
+pub mod asm;
+pub mod bigint;
+pub mod binary;
+pub mod cfg;
+pub mod compile;
+pub mod debug;
+pub mod device;
+pub mod disasm;
+pub mod load;
+pub mod network;
+pub mod predecode;
+pub mod profile;
+pub mod replay;
+pub mod scheduler;
+pub mod snapshot;
+pub mod terminal;
+pub mod threaded;
+pub mod trace;
+pub mod word;
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+pub use self::word::Word;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum AddrMode {
+    POSITION,
+    IMMEDIATE,
+    RELATIVE
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum Opcode {
+    ADD, MUL, IN, OUT, JNZ, JZ, LT, EQ, ARB, HLT
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 10] = [
+        Opcode::ADD, Opcode::MUL, Opcode::IN, Opcode::OUT, Opcode::JNZ,
+        Opcode::JZ, Opcode::LT, Opcode::EQ, Opcode::ARB, Opcode::HLT,
+    ];
+
+    pub fn decode(instruction: i64) -> Result<Opcode, ErrorKind> {
+        Ok(match instruction % 100 {
+            1 => Opcode::ADD,
+            2 => Opcode::MUL,
+            3 => Opcode::IN,
+            4 => Opcode::OUT,
+            5 => Opcode::JNZ,
+            6 => Opcode::JZ,
+            7 => Opcode::LT,
+            8 => Opcode::EQ,
+            9 => Opcode::ARB,
+            99 => Opcode::HLT,
+            other => return Err(ErrorKind::InvalidOpcode(other)),
+        })
+    }
+
+    pub fn code(self) -> i64 {
+        match self {
+            Opcode::ADD => 1,
+            Opcode::MUL => 2,
+            Opcode::IN => 3,
+            Opcode::OUT => 4,
+            Opcode::JNZ => 5,
+            Opcode::JZ => 6,
+            Opcode::LT => 7,
+            Opcode::EQ => 8,
+            Opcode::ARB => 9,
+            Opcode::HLT => 99,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::ADD => "add",
+            Opcode::MUL => "mul",
+            Opcode::IN => "in",
+            Opcode::OUT => "out",
+            Opcode::JNZ => "jnz",
+            Opcode::JZ => "jz",
+            Opcode::LT => "lt",
+            Opcode::EQ => "eq",
+            Opcode::ARB => "arb",
+            Opcode::HLT => "hlt",
+        }
+    }
+
+    pub fn param_count(self) -> usize {
+        match self {
+            Opcode::ADD | Opcode::MUL | Opcode::LT | Opcode::EQ => 3,
+            Opcode::JNZ | Opcode::JZ => 2,
+            Opcode::IN | Opcode::OUT | Opcode::ARB => 1,
+            Opcode::HLT => 0,
+        }
+    }
+
+    // Index of the parameter the instruction writes to, if any.
+    pub fn write_param(self) -> Option<usize> {
+        match self {
+            Opcode::ADD | Opcode::MUL | Opcode::LT | Opcode::EQ => Some(2),
+            Opcode::IN => Some(0),
+            _ => None,
+        }
+    }
+}
+
+// The arithmetic an Overflow came from.  Relative addresses are additions
+// to the relative base, whatever the instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArithOp {
+    Add,
+    Mul,
+}
+
+impl ArithOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ArithOp::Add => "+",
+            ArithOp::Mul => "*",
+        }
+    }
+}
+
+// Errors carry the words involved, so a machine's errors use its own word
+// type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind<W = i64> {
+    InvalidOpcode(W),
+    InvalidMode(i64),
+    ImmediateWrite,
+    Overflow { op: ArithOp, lhs: W, rhs: W },
+    NegativeAddress(W),
+    AddressTooLarge(W),
+    // a run hit its Limits after this many instructions
+    BudgetExhausted(u64),
+    // the program asked for more input than it was given
+    InputExhausted,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IntcodeError<W = i64> {
+    pub pc: usize,
+    pub instruction: W,
+    pub kind: ErrorKind<W>,
+}
+
+impl<W: Word> fmt::Display for IntcodeError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at {} (instruction {}): ", self.pc, self.instruction)?;
+        match self.kind {
+            ErrorKind::InvalidOpcode(ref opcode) => write!(f, "invalid opcode {}", opcode),
+            ErrorKind::InvalidMode(mode) => write!(f, "invalid mode {}", mode),
+            ErrorKind::ImmediateWrite => write!(f, "write in immediate mode"),
+            ErrorKind::Overflow { op, ref lhs, ref rhs } =>
+                write!(f, "overflow {}{}{}", lhs, op.symbol(), rhs),
+            ErrorKind::NegativeAddress(ref addr) => write!(f, "negative address {}", addr),
+            ErrorKind::AddressTooLarge(ref addr) =>
+                write!(f, "address {} beyond the memory limit", addr),
+            ErrorKind::BudgetExhausted(executed) =>
+                write!(f, "budget exhausted after {} instructions", executed),
+            ErrorKind::InputExhausted => write!(f, "out of input"),
+        }
+    }
+}
+
+impl<W: Word> Error for IntcodeError<W> {}
+
+// The same error for a machine with another word type.  Only the decoding
+// helpers below produce i64 errors, and an opcode is two digits, which
+// every word holds.
+fn word_kind<W: Word>(kind: ErrorKind) -> ErrorKind<W> {
+    match kind {
+        ErrorKind::InvalidOpcode(opcode) => ErrorKind::InvalidOpcode(W::from(opcode as i32)),
+        ErrorKind::InvalidMode(mode) => ErrorKind::InvalidMode(mode),
+        ErrorKind::ImmediateWrite => ErrorKind::ImmediateWrite,
+        other => unreachable!("{:?} is not a decoding error", other),
+    }
+}
+
+// For a write beyond a memory's limit.  A machine reports the word the
+// program addressed instead, so this only saturates, at the largest i32,
+// for direct writes past what the word holds.
+fn too_large<W: Word>(addr: usize) -> ErrorKind<W> {
+    let word = if addr as u64 > i64::MAX as u64 { None } else { W::from_i64(addr as i64) };
+    ErrorKind::AddressTooLarge(word.unwrap_or_else(|| W::from(i32::MAX)))
+}
+
+fn to_addr<W: Word>(addr: &W) -> Result<usize, ErrorKind<W>> {
+    match addr.to_i64() {
+        Some(val) if val >= 0 => Ok(val as usize),
+        _ if *addr < W::from(0) => Err(ErrorKind::NegativeAddress(addr.clone())),
+        _ => Err(ErrorKind::AddressTooLarge(addr.clone())),
+    }
+}
+
+fn read_direct<W: Word>(mem: &[W], pos: usize) -> W {
+    if pos >= mem.len() {
+        W::from(0)
+    }
+    else {
+        mem[pos].clone()
+    }
+}
+
+// Storage behind a Machine.  Every address reads as 0 until it is written.
+pub trait Memory {
+    type Word: Word;
+    fn read(&self, addr: usize) -> Self::Word;
+    fn write(&mut self, addr: usize, val: Self::Word) -> Result<(), ErrorKind<Self::Word>>;
+    // Runs of words that may be nonzero, as (start address, words) in
+    // address order.
+    fn segments(&self) -> Vec<(usize, &[Self::Word])>;
+}
+
+// Largest address a plain vector grows to, so a stray write fails instead of
+// trying to allocate all the memory up to it.
+pub const DENSE_MAX_ADDR: usize = (1 << 24) - 1;
+
+// Plain dense memory, grown on write.  interpret() runs on this so it can
+// hand the final memory back as a vector.
+impl<W: Word> Memory for Vec<W> {
+    type Word = W;
+
+    fn read(&self, addr: usize) -> W {
+        read_direct(self, addr)
+    }
+
+    fn write(&mut self, addr: usize, val: W) -> Result<(), ErrorKind<W>> {
+        if addr > DENSE_MAX_ADDR {
+            return Err(too_large(addr));
+        }
+        if addr >= self.len() {
+            self.resize(addr + 1, W::from(0));
+        }
+        self[addr] = val;
+        Ok(())
+    }
+
+    fn segments(&self) -> Vec<(usize, &[W])> {
+        vec![(0, &self[..])]
+    }
+}
+
+pub const DEFAULT_MAX_ADDR: usize = (1 << 40) - 1;
+
+const PAGE_BITS: usize = 10;
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+
+// The program image as a dense vector, and everything past it in fixed-size
+// pages allocated on first write, so a program can use addresses far beyond
+// its image without the memory in between.  Writes past `max_addr` fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PagedMemory<W = i64> {
+    image: Vec<W>,
+    pages: HashMap<usize, Box<[W]>>,
+    pub max_addr: usize,
+}
+
+impl<W: Word> PagedMemory<W> {
+    pub fn new(image: Vec<W>) -> PagedMemory<W> {
+        PagedMemory::with_max_addr(image, DEFAULT_MAX_ADDR)
+    }
+
+    pub fn with_max_addr(image: Vec<W>, max_addr: usize) -> PagedMemory<W> {
+        PagedMemory { image, pages: HashMap::new(), max_addr }
+    }
+
+    pub fn image(&self) -> &[W] {
+        &self.image
+    }
+
+    // Number of pages allocated past the image.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+}
+
+impl<W: Word> Memory for PagedMemory<W> {
+    type Word = W;
+
+    fn read(&self, addr: usize) -> W {
+        if addr < self.image.len() {
+            return self.image[addr].clone();
+        }
+        match self.pages.get(&(addr >> PAGE_BITS)) {
+            Some(page) => page[addr & (PAGE_SIZE - 1)].clone(),
+            None => W::from(0),
+        }
+    }
+
+    fn write(&mut self, addr: usize, val: W) -> Result<(), ErrorKind<W>> {
+        if addr < self.image.len() {
+            self.image[addr] = val;
+            return Ok(());
+        }
+        if addr > self.max_addr {
+            return Err(too_large(addr));
+        }
+        let page = self.pages.entry(addr >> PAGE_BITS)
+            .or_insert_with(|| vec![W::from(0); PAGE_SIZE].into_boxed_slice());
+        page[addr & (PAGE_SIZE - 1)] = val;
+        Ok(())
+    }
+
+    fn segments(&self) -> Vec<(usize, &[W])> {
+        let mut ret = vec![(0, &self.image[..])];
+        let mut pages: Vec<(&usize, &Box<[W]>)> = self.pages.iter().collect();
+        pages.sort_by_key(|&(&num, _)| num);
+        for (&num, page) in pages {
+            // the first page may overlap the end of the image
+            let start = std::cmp::max(num << PAGE_BITS, self.image.len());
+            ret.push((start, &page[start - (num << PAGE_BITS)..]));
+        }
+        ret
+    }
+}
+
+fn relative<W: Word>(relbase: &W, val: &W) -> Result<W, ErrorKind<W>> {
+    relbase.checked_add(val).ok_or_else(|| ErrorKind::Overflow {
+        op: ArithOp::Add,
+        lhs: relbase.clone(),
+        rhs: val.clone(),
+    })
+}
+
+fn mem_follow_mode<M: Memory>(val: M::Word, mem: &M, relbase: &M::Word, mode: AddrMode)
+                              -> Result<M::Word, ErrorKind<M::Word>> {
+    Ok(match mode {
+        AddrMode::POSITION => mem.read(to_addr(&val)?),
+        AddrMode::IMMEDIATE => val,
+        AddrMode::RELATIVE => mem.read(to_addr(&relative(relbase, &val)?)?),
+    })
+}
+
+pub fn mem_read<M: Memory>(mem: &M, pos: usize, relbase: &M::Word, mode: AddrMode)
+                           -> Result<M::Word, ErrorKind<M::Word>> {
+    mem_follow_mode(mem.read(pos), mem, relbase, mode)
+}
+
+pub fn decode_mode(digit: i64) -> Result<AddrMode, ErrorKind> {
+    match digit {
+        0 => Ok(AddrMode::POSITION),
+        1 => Ok(AddrMode::IMMEDIATE),
+        2 => Ok(AddrMode::RELATIVE),
+        other => Err(ErrorKind::InvalidMode(other)),
+    }
+}
+
+pub fn encode_mode(mode: AddrMode) -> i64 {
+    match mode {
+        AddrMode::POSITION => 0,
+        AddrMode::IMMEDIATE => 1,
+        AddrMode::RELATIVE => 2,
+    }
+}
+
+// The instruction at `pos` as an i64, which any valid instruction fits in.
+fn instruction_at<M: Memory>(mem: &M, pos: usize) -> Result<i64, ErrorKind<M::Word>> {
+    let word = mem.read(pos);
+    word.to_i64().ok_or(ErrorKind::InvalidOpcode(word))
+}
+
+fn no_more_modes(modes: i64) -> Result<(), ErrorKind> {
+    if modes != 0 {
+        return Err(ErrorKind::InvalidMode(modes));
+    }
+    Ok(())
+}
+
+// Decode an instruction's opcode and parameter modes.  The interpreter and
+// every tool decode through here, so they agree on what is an instruction:
+// unused mode digits and immediate-mode writes are errors.  Modes past the
+// opcode's parameters are POSITION.
+pub fn decode_instruction(instruction: i64) -> Result<(Opcode, [AddrMode; 3]), ErrorKind> {
+    let opcode = Opcode::decode(instruction)?;
+    let mut modes = instruction / 100;
+    let mut ret = [AddrMode::POSITION; 3];
+    for (i, slot) in ret.iter_mut().enumerate().take(opcode.param_count()) {
+        let mode = decode_mode(modes % 10)?;
+        if mode == AddrMode::IMMEDIATE && opcode.write_param() == Some(i) {
+            return Err(ErrorKind::ImmediateWrite);
+        }
+        *slot = mode;
+        modes /= 10;
+    }
+    no_more_modes(modes)?;
+    Ok((opcode, ret))
+}
+
+// decode_instruction() with only the opcode's own modes.
+pub fn decode_param_modes(instruction: i64) -> Result<(Opcode, Vec<AddrMode>), ErrorKind> {
+    let (opcode, modes) = decode_instruction(instruction)?;
+    Ok((opcode, modes[..opcode.param_count()].to_vec()))
+}
+
+// Inverse of decode_param_modes().
+pub fn encode_instruction(opcode: Opcode, modes: &[AddrMode]) -> i64 {
+    let mut instruction = opcode.code();
+    let mut scale = 100;
+    for &mode in modes {
+        instruction += encode_mode(mode) * scale;
+        scale *= 10;
+    }
+    instruction
+}
+
+fn mem_write<M: Memory>(mem: &mut M, relbase: &M::Word, raw_addr: &M::Word, mode: AddrMode,
+                        val: M::Word) -> Result<(), ErrorKind<M::Word>> {
+    let addr = match mode {
+        AddrMode::POSITION => raw_addr.clone(),
+        AddrMode::RELATIVE => relative(relbase, raw_addr)?,
+        AddrMode::IMMEDIATE => return Err(ErrorKind::ImmediateWrite),
+    };
+    mem.write(to_addr(&addr)?, val).map_err(|err| match err {
+        ErrorKind::AddressTooLarge(_) => ErrorKind::AddressTooLarge(addr),
+        other => other,
+    })
+}
+
+// Limits on a single run, unlimited by default.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub instructions: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+// Instructions between looks at the clock.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Event<W = i64> {
+    NeedInput,
+    Output(W),
+    Halted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine<M: Memory = PagedMemory> {
+    pub mem: M,
+    pub pos: usize,
+    pub relbase: M::Word,
+    pub input: VecDeque<M::Word>,
+}
+
+impl Machine {
+    pub fn new(prog: &[i64]) -> Machine {
+        Machine::from_mem(prog.to_vec())
+    }
+
+    pub fn from_mem(mem: Vec<i64>) -> Machine {
+        Machine::with_memory(PagedMemory::new(mem))
+    }
+}
+
+impl<M: Memory> Machine<M> {
+    pub fn with_memory(mem: M) -> Machine<M> {
+        Machine { mem, pos: 0, relbase: M::Word::from(0), input: VecDeque::new() }
+    }
+
+    pub fn push_input(&mut self, val: M::Word) {
+        self.input.push_back(val);
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.mem.read(self.pos) == M::Word::from(99)
+    }
+
+    // Address the current instruction will write to, if it writes memory.
+    pub fn write_addr(&self) -> Option<M::Word> {
+        let instruction = self.mem.read(self.pos).to_i64()?;
+        let (opcode, modes) = decode_instruction(instruction).ok()?;
+        let i = opcode.write_param()?;
+        let raw_addr = self.mem.read(self.pos + 1 + i);
+        match modes[i] {
+            AddrMode::RELATIVE => self.relbase.checked_add(&raw_addr),
+            _ => Some(raw_addr),
+        }
+    }
+
+    // Execute a single instruction, returning the event it caused, if any.
+    // An input instruction with an empty input queue is not executed, so
+    // the machine can be resumed after the input is provided.
+    pub fn step(&mut self) -> Result<Option<Event<M::Word>>, IntcodeError<M::Word>> {
+        let pc = self.pos;
+        self.execute().map_err(|kind| IntcodeError {
+            pc,
+            instruction: self.mem.read(pc),
+            kind,
+        })
+    }
+
+    fn execute(&mut self) -> Result<Option<Event<M::Word>>, ErrorKind<M::Word>> {
+        if self.is_halted() {
+            return Ok(Some(Event::Halted));
+        }
+        let mem = &mut self.mem;
+        let pos = self.pos;
+        let relbase = &self.relbase;
+        let instruction = instruction_at(mem, pos)?;
+        let (opcode, modes) = decode_instruction(instruction).map_err(word_kind)?;
+        let next = pos + 1 + opcode.param_count();
+        let zero = M::Word::from(0);
+        let read = |mem: &M, i: usize| mem_read(mem, pos + 1 + i, relbase, modes[i]);
+        let write = |mem: &mut M, i: usize, val| {
+            let dest = mem.read(pos + 1 + i);
+            mem_write(mem, relbase, &dest, modes[i], val)
+        };
+        match opcode {
+            Opcode::ADD => {
+                let (op1, op2) = (read(mem, 0)?, read(mem, 1)?);
+                let val = op1.checked_add(&op2).ok_or_else(|| ErrorKind::Overflow {
+                    op: ArithOp::Add,
+                    lhs: op1.clone(),
+                    rhs: op2.clone(),
+                })?;
+                write(mem, 2, val)?;
+                self.pos = next;
+            }
+            Opcode::MUL => {
+                let (op1, op2) = (read(mem, 0)?, read(mem, 1)?);
+                let val = op1.checked_mul(&op2).ok_or_else(|| ErrorKind::Overflow {
+                    op: ArithOp::Mul,
+                    lhs: op1.clone(),
+                    rhs: op2.clone(),
+                })?;
+                write(mem, 2, val)?;
+                self.pos = next;
+            }
+            Opcode::IN => {
+                // the input is only taken once it has been stored, so a bad
+                // destination leaves it queued
+                let val = match self.input.front() {
+                    Some(val) => val.clone(),
+                    None => return Ok(Some(Event::NeedInput)),
+                };
+                write(mem, 0, val)?;
+                self.input.pop_front();
+                self.pos = next;
+            }
+            Opcode::OUT => {
+                let val = read(mem, 0)?;
+                self.pos = next;
+                return Ok(Some(Event::Output(val)));
+            }
+            Opcode::JNZ | Opcode::JZ => {
+                let (op1, op2) = (read(mem, 0)?, read(mem, 1)?);
+                if (op1 != zero) == (opcode == Opcode::JNZ) {
+                    self.pos = to_addr(&op2)?;
+                }
+                else {
+                    self.pos = next;
+                }
+            }
+            Opcode::LT => {
+                let (op1, op2) = (read(mem, 0)?, read(mem, 1)?);
+                write(mem, 2, M::Word::from(if op1 < op2 {1} else {0}))?;
+                self.pos = next;
+            }
+            Opcode::EQ => {
+                let (op1, op2) = (read(mem, 0)?, read(mem, 1)?);
+                write(mem, 2, M::Word::from(if op1 == op2 {1} else {0}))?;
+                self.pos = next;
+            }
+            Opcode::ARB => {
+                self.relbase = relative(relbase, &read(mem, 0)?)?;
+                self.pos = next;
+            }
+            // only a bare 99 decodes as hlt, and is_halted() caught that
+            Opcode::HLT => return Ok(Some(Event::Halted)),
+        };
+        Ok(None)
+    }
+
+    // Run until the machine produces output, needs input, or halts.
+    pub fn run(&mut self) -> Result<Event<M::Word>, IntcodeError<M::Word>> {
+        loop {
+            if let Some(event) = self.step()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    // Run to completion, obtaining input and delivering output through
+    // callbacks.
+    pub fn run_with(&mut self,
+                    read_input: &mut dyn FnMut() -> M::Word,
+                    write_output: &mut dyn FnMut(M::Word)) -> Result<(), IntcodeError<M::Word>> {
+        loop {
+            match self.run()? {
+                Event::NeedInput => self.push_input(read_input()),
+                Event::Output(val) => write_output(val),
+                Event::Halted => return Ok(()),
+            }
+        }
+    }
+
+    // Iterator over the outputs, running the machine only as far as the
+    // next one and taking input from `inputs` when the program asks for it.
+    // Asking for more input than there is fails with InputExhausted.
+    pub fn outputs<I>(self, inputs: I) -> Outputs<I::IntoIter, M>
+        where I: IntoIterator<Item = M::Word> {
+        Outputs { machine: self, inputs: inputs.into_iter(), done: false }
+    }
+
+    // Like run_with(), but fails with BudgetExhausted when a limit is hit.
+    // The error's pc is the instruction that was not run, and the machine
+    // can carry on from there.
+    pub fn run_limited(&mut self, limits: Limits,
+                       read_input: &mut dyn FnMut() -> M::Word,
+                       write_output: &mut dyn FnMut(M::Word))
+                       -> Result<(), IntcodeError<M::Word>> {
+        let deadline = limits.time.map(|time| Instant::now() + time);
+        let mut executed = 0;
+        loop {
+            if self.is_halted() {
+                return Ok(());
+            }
+            let over_count = limits.instructions.is_some_and(|max| executed >= max);
+            let over_time = executed % TIME_CHECK_INTERVAL == 0
+                && deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            if over_count || over_time {
+                return Err(IntcodeError {
+                    pc: self.pos,
+                    instruction: self.mem.read(self.pos),
+                    kind: ErrorKind::BudgetExhausted(executed),
+                });
+            }
+            match self.step()? {
+                Some(Event::NeedInput) => {
+                    // the input instruction runs on the next step
+                    self.push_input(read_input());
+                    continue;
+                }
+                Some(Event::Output(val)) => write_output(val),
+                _ => (),
+            }
+            executed += 1;
+        }
+    }
+}
+
+pub struct Outputs<I, M: Memory = PagedMemory> {
+    pub machine: Machine<M>,
+    inputs: I,
+    // halted or failed
+    done: bool,
+}
+
+impl<I: Iterator<Item = M::Word>, M: Memory> Iterator for Outputs<I, M> {
+    type Item = Result<M::Word, IntcodeError<M::Word>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let event = self.machine.run();
+            match event {
+                Ok(Event::Output(val)) => return Some(Ok(val)),
+                Ok(Event::NeedInput) => match self.inputs.next() {
+                    Some(val) => self.machine.push_input(val),
+                    None => {
+                        self.done = true;
+                        let pc = self.machine.pos;
+                        return Some(Err(IntcodeError {
+                            pc,
+                            instruction: self.machine.mem.read(pc),
+                            kind: ErrorKind::InputExhausted,
+                        }));
+                    }
+                },
+                Ok(Event::Halted) => self.done = true,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+// Run the program in `mem` to completion, leaving the final memory in it.
+pub fn interpret(mem: &mut Vec<i64>,
+                 read_input: &mut dyn FnMut() -> i64,
+                 write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    interpret_words(mem, read_input, write_output)
+}
+
+// interpret() on words of another type.
+pub fn interpret_words<W: Word>(mem: &mut Vec<W>,
+                                read_input: &mut dyn FnMut() -> W,
+                                write_output: &mut dyn FnMut(W)) -> Result<(), IntcodeError<W>> {
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    let result = machine.run_with(read_input, write_output);
+    *mem = machine.mem;
+    result
+}
+
+// interpret() with limits on the run.
+pub fn interpret_limited(mem: &mut Vec<i64>, limits: Limits,
+                         read_input: &mut dyn FnMut() -> i64,
+                         write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    let result = machine.run_limited(limits, read_input, write_output);
+    *mem = machine.mem;
+    result
+}
@@ -0,0 +1,223 @@
+// Intcode assembler.  The source format is line based:
+//
+//   ; comment
+//   loop:   add [counter], #1, [counter]   ; position, immediate
+//           out rb-1                       ; relative to the relative base
+//           jnz [counter], #loop
+//   counter: data 0, loop+2
+//
+// Operand values and data words can be numbers, labels, or label+N/label-N.
+// A numeric label such as `12:` asserts the current address instead of
+// defining a name, so the output of the disassembler assembles back to the
+// original program.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use super::{AddrMode, Opcode, decode_param_modes, encode_instruction};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.msg)
+    }
+}
+
+impl Error for AsmError {}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Num(i64),
+    Label(String),
+}
+
+// Sum of signed terms.
+type Expr = Vec<(i64, Term)>;
+
+enum Item {
+    Instruction(Opcode, Vec<(AddrMode, Expr)>),
+    Data(Vec<Expr>),
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => (),
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_expr(s: &str) -> Result<Expr, String> {
+    let mut ret = vec![];
+    let mut rest = s.trim();
+    let mut sign = 1;
+    if rest.is_empty() {
+        return Err("missing value".to_string());
+    }
+    loop {
+        if let Some(after) = rest.strip_prefix('-') {
+            sign = -sign;
+            rest = after.trim_start();
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix('+') {
+            rest = after.trim_start();
+            continue;
+        }
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let tok = rest[..end].trim();
+        let term = if let Ok(num) = tok.parse::<i64>() {
+            Term::Num(num)
+        }
+        else if is_label(tok) {
+            Term::Label(tok.to_string())
+        }
+        else {
+            return Err(format!("invalid value {:?}", s.trim()));
+        };
+        ret.push((sign, term));
+        rest = rest[end..].trim_start();
+        if rest.is_empty() {
+            return Ok(ret);
+        }
+        sign = 1;
+    }
+}
+
+fn parse_operand(s: &str) -> Result<(AddrMode, Expr), String> {
+    let s = s.trim();
+    if let Some(expr) = s.strip_prefix('#') {
+        Ok((AddrMode::IMMEDIATE, parse_expr(expr)?))
+    }
+    else if s.starts_with('[') && s.ends_with(']') {
+        Ok((AddrMode::POSITION, parse_expr(&s[1..s.len() - 1])?))
+    }
+    else if s == "rb" {
+        Ok((AddrMode::RELATIVE, vec![(1, Term::Num(0))]))
+    }
+    else if s.starts_with("rb+") || s.starts_with("rb-") {
+        Ok((AddrMode::RELATIVE, parse_expr(&s[2..])?))
+    }
+    else {
+        Err(format!("invalid operand {:?}", s))
+    }
+}
+
+fn parse_item(text: &str) -> Result<Item, String> {
+    let (name, args) = match text.find(char::is_whitespace) {
+        Some(pos) => (&text[..pos], text[pos..].trim()),
+        None => (text, ""),
+    };
+    let args: Vec<&str> = if args.is_empty() {
+        vec![]
+    }
+    else {
+        args.split(",").collect()
+    };
+    if name == "data" {
+        if args.is_empty() {
+            return Err("data needs at least one value".to_string());
+        }
+        return Ok(Item::Data(args.iter()
+                             .map(|arg| parse_expr(arg))
+                             .collect::<Result<_, _>>()?));
+    }
+    let opcode = Opcode::ALL.iter().cloned()
+        .find(|op| op.mnemonic() == name)
+        .ok_or_else(|| format!("unknown mnemonic {:?}", name))?;
+    if args.len() != opcode.param_count() {
+        return Err(format!("{} takes {} operands, got {}",
+                           name, opcode.param_count(), args.len()));
+    }
+    let operands: Vec<(AddrMode, Expr)> = args.iter()
+        .map(|arg| parse_operand(arg))
+        .collect::<Result<_, _>>()?;
+    if opcode.write_param().map(|i| operands[i].0) == Some(AddrMode::IMMEDIATE) {
+        return Err(format!("{} cannot write to an immediate operand", name));
+    }
+    Ok(Item::Instruction(opcode, operands))
+}
+
+fn eval(expr: &Expr, labels: &HashMap<String, i64>) -> Result<i64, String> {
+    let mut total = 0i64;
+    for &(sign, ref term) in expr {
+        let val = match *term {
+            Term::Num(num) => num,
+            Term::Label(ref name) => *labels.get(name)
+                .ok_or_else(|| format!("undefined label {:?}", name))?,
+        };
+        total = val.checked_mul(sign)
+            .and_then(|val| total.checked_add(val))
+            .ok_or_else(|| "value overflows".to_string())?;
+    }
+    Ok(total)
+}
+
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    let mut labels = HashMap::new();
+    let mut items = vec![];
+    let mut addr = 0usize;
+
+    for (lineno, line) in source.lines().enumerate() {
+        let lineno = lineno + 1;
+        let err = |msg| AsmError { line: lineno, msg };
+        let mut text = match line.find(';') {
+            Some(pos) => &line[..pos],
+            None => line,
+        }.trim();
+        while let Some(colon) = text.find(':') {
+            let label = text[..colon].trim();
+            if let Ok(expected) = label.parse::<usize>() {
+                if expected != addr {
+                    return Err(err(format!("address is {}, not {}", addr, expected)));
+                }
+            }
+            else if is_label(label) {
+                if labels.insert(label.to_string(), addr as i64).is_some() {
+                    return Err(err(format!("duplicate label {:?}", label)));
+                }
+            }
+            else {
+                return Err(err(format!("invalid label {:?}", label)));
+            }
+            text = text[colon + 1..].trim();
+        }
+        if text.is_empty() {
+            continue;
+        }
+        let item = parse_item(text).map_err(err)?;
+        addr += match item {
+            Item::Instruction(_, ref operands) => 1 + operands.len(),
+            Item::Data(ref words) => words.len(),
+        };
+        items.push((lineno, item));
+    }
+
+    let mut ret = vec![];
+    for (lineno, item) in items {
+        let err = |msg| AsmError { line: lineno, msg };
+        match item {
+            Item::Instruction(opcode, operands) => {
+                let modes: Vec<AddrMode> = operands.iter().map(|&(mode, _)| mode).collect();
+                let instruction = encode_instruction(opcode, &modes);
+                debug_assert_eq!(decode_param_modes(instruction), Ok((opcode, modes)));
+                ret.push(instruction);
+                for (_, expr) in operands {
+                    ret.push(eval(&expr, &labels).map_err(err)?);
+                }
+            }
+            Item::Data(words) => {
+                for expr in words {
+                    ret.push(eval(&expr, &labels).map_err(err)?);
+                }
+            }
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,201 @@
+// Arbitrary-precision signed integer, just enough of one to be a Word: it
+// adds, multiplies, compares, prints and parses.  Sign and magnitude, with
+// the magnitude in 32-bit digits, least significant first and without
+// leading zeros, so zero is an empty magnitude.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use super::Word;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u32>,
+}
+
+fn trim(mut mag: Vec<u32>) -> Vec<u32> {
+    while mag.last() == Some(&0) {
+        mag.pop();
+    }
+    mag
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut ret = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        ret.push(sum as u32);
+        carry = sum >> 32;
+    }
+    ret.push(carry as u32);
+    trim(ret)
+}
+
+// a - b, for a at least b
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut ret = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut diff = digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if diff < 0 {
+            diff += 1 << 32;
+            borrow = 1;
+        }
+        ret.push(diff as u32);
+    }
+    trim(ret)
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut ret = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let cur = ret[i + j] as u64 + x as u64 * y as u64 + carry;
+            ret[i + j] = cur as u32;
+            carry = cur >> 32;
+        }
+        ret[i + b.len()] = carry as u32;
+    }
+    trim(ret)
+}
+
+// Divide in place by a small divisor, returning the remainder.
+fn divmod_small(mag: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut rem = 0u64;
+    for digit in mag.iter_mut().rev() {
+        let cur = (rem << 32) | *digit as u64;
+        *digit = (cur / divisor as u64) as u32;
+        rem = cur % divisor as u64;
+    }
+    *mag = trim(std::mem::take(mag));
+    rem as u32
+}
+
+impl BigInt {
+    fn new(negative: bool, mag: Vec<u32>) -> BigInt {
+        let mag = trim(mag);
+        BigInt { negative: negative && !mag.is_empty(), mag }
+    }
+
+    fn from_u128(negative: bool, mut val: u128) -> BigInt {
+        let mut mag = vec![];
+        while val != 0 {
+            mag.push(val as u32);
+            val >>= 32;
+        }
+        BigInt::new(negative, mag)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(val: i64) -> BigInt {
+        BigInt::from_u128(val < 0, val.unsigned_abs() as u128)
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(val: i32) -> BigInt {
+        BigInt::from(val as i64)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.mag.is_empty() {
+            return write!(f, "0");
+        }
+        // nine decimal digits at a time, least significant first
+        let mut mag = self.mag.clone();
+        let mut chunks = vec![];
+        while !mag.is_empty() {
+            chunks.push(divmod_small(&mut mag, 1_000_000_000));
+        }
+        let mut out = String::new();
+        if self.negative {
+            out.push('-');
+        }
+        out.push_str(&chunks.pop().unwrap().to_string());
+        for chunk in chunks.iter().rev() {
+            out.push_str(&format!("{:09}", chunk));
+        }
+        write!(f, "{}", out)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BigInt, String> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("invalid integer {:?}", s));
+        }
+        let mut mag = vec![];
+        for b in digits.bytes() {
+            mag = add_mag(&mul_mag(&mag, &[10]), &[(b - b'0') as u32]);
+        }
+        Ok(BigInt::new(negative, mag))
+    }
+}
+
+impl Word for BigInt {
+    fn from_i64(val: i64) -> Option<BigInt> {
+        Some(BigInt::from(val))
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        if self.mag.len() > 2 {
+            return None;
+        }
+        let mag = self.mag.iter().rev().fold(0i128, |acc, &digit| acc << 32 | digit as i128);
+        let val = if self.negative {-mag} else {mag};
+        if val < i64::MIN as i128 || val > i64::MAX as i128 {
+            None
+        }
+        else {
+            Some(val as i64)
+        }
+    }
+
+    fn checked_add(&self, other: &BigInt) -> Option<BigInt> {
+        if self.negative == other.negative {
+            return Some(BigInt::new(self.negative, add_mag(&self.mag, &other.mag)));
+        }
+        Some(match cmp_mag(&self.mag, &other.mag) {
+            Ordering::Less => BigInt::new(other.negative, sub_mag(&other.mag, &self.mag)),
+            _ => BigInt::new(self.negative, sub_mag(&self.mag, &other.mag)),
+        })
+    }
+
+    fn checked_mul(&self, other: &BigInt) -> Option<BigInt> {
+        Some(BigInt::new(self.negative != other.negative, mul_mag(&self.mag, &other.mag)))
+    }
+}
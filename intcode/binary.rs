@@ -0,0 +1,238 @@
+// Compact binary form of a program image, or of a complete machine state
+// like snapshot's, for images too large to parse quickly as text.  Numbers
+// are LEB128 varints, 7 bits to a byte, and signed values are zigzag coded
+// first so small negative numbers stay short too:
+//
+//   magic     "ICB"
+//   version   1
+//   word      bytes per word of the machine it was written from, 8 for i64
+//   flags     bit 0 set when a machine state follows the image
+//   count     number of image words
+//   words     the image, signed
+//
+// and with the state flag:
+//
+//   pos       the program counter
+//   relbase   signed
+//   input     count, then the queued input, signed
+//   pages     count, then for each page its start address, count and
+//             words, signed, for memory written past the image
+//
+// Images load as i64 words, so word sizes up to 8 bytes load, and every
+// word must fit the size its image declares.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use super::{Machine, Memory};
+
+pub const MAGIC: &[u8] = b"ICB";
+const VERSION: u8 = 1;
+const WORD_BYTES: u8 = 8;
+const FLAG_STATE: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryError {
+    pub offset: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "binary image byte {}: {}", self.offset, self.msg)
+    }
+}
+
+impl Error for BinaryError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contents {
+    Image(Vec<i64>),
+    Snapshot(Machine),
+}
+
+fn zigzag(val: i64) -> u64 {
+    ((val << 1) ^ (val >> 63)) as u64
+}
+
+fn unzigzag(val: u64) -> i64 {
+    (val >> 1) as i64 ^ -((val & 1) as i64)
+}
+
+fn put_varint(out: &mut Vec<u8>, mut val: u64) {
+    while val >= 0x80 {
+        out.push(val as u8 | 0x80);
+        val >>= 7;
+    }
+    out.push(val as u8);
+}
+
+fn put_words(out: &mut Vec<u8>, words: &[i64]) {
+    put_varint(out, words.len() as u64);
+    for &val in words {
+        put_varint(out, zigzag(val));
+    }
+}
+
+fn header(flags: u8) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&[VERSION, WORD_BYTES, flags]);
+    out
+}
+
+pub fn image_to_bytes(words: &[i64]) -> Vec<u8> {
+    let mut out = header(0);
+    put_words(&mut out, words);
+    out
+}
+
+pub fn snapshot_to_bytes<M: Memory<Word = i64>>(machine: &Machine<M>) -> Vec<u8> {
+    let segments = machine.mem.segments();
+    let image = segments.iter().find(|&&(start, _)| start == 0).map_or(&[][..], |seg| seg.1);
+    let pages: Vec<_> = segments.iter().filter(|&&(start, _)| start != 0).collect();
+    let input: Vec<i64> = machine.input.iter().cloned().collect();
+
+    let mut out = header(FLAG_STATE);
+    put_words(&mut out, image);
+    put_varint(&mut out, machine.pos as u64);
+    put_varint(&mut out, zigzag(machine.relbase));
+    put_words(&mut out, &input);
+    put_varint(&mut out, pages.len() as u64);
+    for &&(start, words) in &pages {
+        put_varint(&mut out, start as u64);
+        put_words(&mut out, words);
+    }
+    out
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    // bytes per word, from the header
+    word_bytes: u8,
+}
+
+impl<'a> Reader<'a> {
+    fn err<T>(&self, msg: String) -> Result<T, BinaryError> {
+        Err(BinaryError { offset: self.offset, msg })
+    }
+
+    fn byte(&mut self, what: &str) -> Result<u8, BinaryError> {
+        match self.bytes.get(self.offset) {
+            Some(&b) => {
+                self.offset += 1;
+                Ok(b)
+            }
+            None => self.err(format!("truncated in {}", what)),
+        }
+    }
+
+    fn varint(&mut self, what: &str) -> Result<u64, BinaryError> {
+        let start = self.offset;
+        let mut val = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte(what)?;
+            if shift == 63 && b > 1 {
+                break;
+            }
+            val |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(val);
+            }
+        }
+        self.offset = start;
+        self.err(format!("{} out of range", what))
+    }
+
+    fn usize(&mut self, what: &str) -> Result<usize, BinaryError> {
+        let start = self.offset;
+        let val = self.varint(what)?;
+        if val > usize::MAX as u64 {
+            self.offset = start;
+            return self.err(format!("{} out of range", what));
+        }
+        Ok(val as usize)
+    }
+
+    // A signed word, which must fit the image's word size.
+    fn word(&mut self, what: &str) -> Result<i64, BinaryError> {
+        let start = self.offset;
+        let val = unzigzag(self.varint(what)?);
+        let bits = self.word_bytes as u32 * 8;
+        if bits < 64 && (val < -(1 << (bits - 1)) || val >= 1 << (bits - 1)) {
+            self.offset = start;
+            return self.err(format!("{} word {} does not fit {} bytes", what, val, self.word_bytes));
+        }
+        Ok(val)
+    }
+
+    fn words(&mut self, what: &str) -> Result<Vec<i64>, BinaryError> {
+        let count = self.usize(what)?;
+        // every word takes at least a byte, which bounds the allocation
+        if count > self.bytes.len() - self.offset {
+            return self.err(format!("truncated in {}", what));
+        }
+        let mut ret = Vec::with_capacity(count);
+        for _ in 0..count {
+            ret.push(self.word(what)?);
+        }
+        Ok(ret)
+    }
+}
+
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Result<Contents, BinaryError> {
+    let mut reader = Reader { bytes, offset: 0, word_bytes: WORD_BYTES };
+    if !is_binary(bytes) {
+        return reader.err("not an intcode binary image".to_string());
+    }
+    reader.offset = MAGIC.len();
+    let version = reader.byte("header")?;
+    if version != VERSION {
+        reader.offset -= 1;
+        return reader.err(format!("unsupported version {}", version));
+    }
+    let word_bytes = reader.byte("header")?;
+    if word_bytes == 0 || word_bytes > WORD_BYTES {
+        reader.offset -= 1;
+        return reader.err(format!("unsupported word size {}, only up to {} bytes load",
+                                  word_bytes, WORD_BYTES));
+    }
+    reader.word_bytes = word_bytes;
+    let flags = reader.byte("header")?;
+    if flags & !FLAG_STATE != 0 {
+        reader.offset -= 1;
+        return reader.err(format!("unknown flags {:#x}", flags));
+    }
+    let image = reader.words("image")?;
+    let contents = if flags & FLAG_STATE == 0 {
+        Contents::Image(image)
+    }
+    else {
+        let mut machine = Machine::from_mem(image);
+        machine.pos = reader.usize("pos")?;
+        machine.relbase = reader.word("relbase")?;
+        machine.input = reader.words("input")?.into_iter().collect();
+        for _ in 0..reader.usize("pages")? {
+            let start = reader.usize("page")?;
+            for (i, val) in reader.words("page")?.into_iter().enumerate() {
+                let written = start.checked_add(i).map(|addr| machine.mem.write(addr, val));
+                if written.is_none_or(|result| result.is_err()) {
+                    return reader.err("page beyond the memory limit".to_string());
+                }
+            }
+        }
+        Contents::Snapshot(machine)
+    };
+    if reader.offset != bytes.len() {
+        return reader.err("trailing bytes".to_string());
+    }
+    Ok(contents)
+}
+
+pub fn load_file(path: &str) -> Result<Contents, Box<dyn Error>> {
+    Ok(from_bytes(&fs::read(path)?)?)
+}
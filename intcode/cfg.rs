@@ -0,0 +1,195 @@
+// Static control-flow graph of an Intcode program.  Code is what
+// disasm::reachable() finds; it is split into basic blocks at jump targets
+// and after jumps, and blocks are linked by fall-through and by jnz/jz
+// targets given as immediate operands.  Jumps through memory or the
+// relative base cannot be followed and are flagged as indirect.
+//
+// The analysis also finds self-modifying code: instructions with a word
+// that some position-mode write stores to.  Relative-mode writes are not
+// resolved.
+
+use std::collections::{BTreeMap, BTreeSet};
+use super::{AddrMode, Opcode};
+use super::disasm::{reachable, Instruction};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub start: usize,
+    pub instrs: Vec<(usize, Instruction)>,
+    // start addresses of the blocks control can pass to
+    pub succs: Vec<usize>,
+    // ends in a jump whose target is not an immediate operand
+    pub indirect: bool,
+}
+
+// A write by the instruction at `writer` to word `addr` of the instruction
+// at `instr`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CodeWrite {
+    pub writer: usize,
+    pub addr: usize,
+    pub instr: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cfg {
+    pub blocks: BTreeMap<usize, Block>,
+    pub code_writes: Vec<CodeWrite>,
+}
+
+fn is_jump(instr: &Instruction) -> bool {
+    instr.opcode == Opcode::JNZ || instr.opcode == Opcode::JZ
+}
+
+// Whether a jnz/jz can take its jump, judging by an immediate condition.
+fn may_jump(instr: &Instruction) -> bool {
+    match instr.params[0] {
+        (AddrMode::IMMEDIATE, cond) => (cond != 0) == (instr.opcode == Opcode::JNZ),
+        _ => true,
+    }
+}
+
+pub fn analyze(mem: &[i64]) -> Cfg {
+    let code: Vec<(usize, Instruction)> = reachable(mem).into_iter()
+        .enumerate()
+        .filter_map(|(pos, instr)| instr.map(|instr| (pos, instr)))
+        .collect();
+    let starts: BTreeSet<usize> = code.iter().map(|&(pos, _)| pos).collect();
+
+    let mut leaders = BTreeSet::new();
+    leaders.insert(0);
+    for &(pos, ref instr) in &code {
+        if is_jump(instr) || instr.opcode == Opcode::HLT {
+            leaders.insert(pos + instr.len());
+        }
+        if let Some(target) = instr.jump_target() {
+            if target >= 0 && may_jump(instr) {
+                leaders.insert(target as usize);
+            }
+        }
+    }
+
+    let mut blocks: BTreeMap<usize, Block> = BTreeMap::new();
+    let mut current: Option<Block> = None;
+    for (pos, instr) in code.iter().cloned() {
+        let continues = match current {
+            Some(ref block) => {
+                let &(last_pos, ref last) = block.instrs.last().unwrap();
+                last_pos + last.len() == pos && !leaders.contains(&pos)
+            }
+            None => false,
+        };
+        if !continues {
+            if let Some(block) = current.take() {
+                blocks.insert(block.start, block);
+            }
+            current = Some(Block { start: pos, instrs: vec![], succs: vec![], indirect: false });
+        }
+        current.as_mut().unwrap().instrs.push((pos, instr));
+    }
+    if let Some(block) = current {
+        blocks.insert(block.start, block);
+    }
+
+    for block in blocks.values_mut() {
+        let (pos, last) = block.instrs.last().cloned().unwrap();
+        let next = pos + last.len();
+        if is_jump(&last) && may_jump(&last) {
+            match last.jump_target() {
+                Some(target) if target >= 0 && starts.contains(&(target as usize)) =>
+                    block.succs.push(target as usize),
+                Some(_) => (),
+                None => block.indirect = true,
+            }
+        }
+        if last.falls_through() && starts.contains(&next) && !block.succs.contains(&next) {
+            block.succs.push(next);
+        }
+    }
+
+    // instruction containing each code word
+    let mut owner = BTreeMap::new();
+    for &(pos, ref instr) in &code {
+        for addr in pos..pos + instr.len() {
+            owner.insert(addr, pos);
+        }
+    }
+    let mut code_writes = vec![];
+    for &(writer, ref instr) in &code {
+        if let Some(i) = instr.opcode.write_param() {
+            if let (AddrMode::POSITION, addr) = instr.params[i] {
+                if addr >= 0 {
+                    if let Some(&target) = owner.get(&(addr as usize)) {
+                        code_writes.push(CodeWrite { writer, addr: addr as usize, instr: target });
+                    }
+                }
+            }
+        }
+    }
+
+    Cfg { blocks, code_writes }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Cfg {
+    pub fn indirect_jumps(&self) -> Vec<usize> {
+        self.blocks.values()
+            .filter(|block| block.indirect)
+            .map(|block| block.instrs.last().unwrap().0)
+            .collect()
+    }
+
+    // Graphviz source.  Blocks with self-modified instructions are red, and
+    // indirect jumps lead to a separate "indirect" node.
+    pub fn to_dot(&self) -> String {
+        let modified: BTreeSet<usize> = self.code_writes.iter().map(|w| w.instr).collect();
+        let mut out = String::from("digraph intcode {\n");
+        out.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+        for block in self.blocks.values() {
+            let mut label = String::new();
+            for &(pos, ref instr) in &block.instrs {
+                let mark = if modified.contains(&pos) {" *"} else {""};
+                label.push_str(&escape(&format!("{}: {}{}", pos, instr, mark)));
+                label.push_str("\\l");
+            }
+            let color = if block.instrs.iter().any(|&(pos, _)| modified.contains(&pos)) {
+                ", color=red"
+            }
+            else {
+                ""
+            };
+            out.push_str(&format!("    b{} [label=\"{}\"{}];\n", block.start, label, color));
+        }
+        if self.blocks.values().any(|block| block.indirect) {
+            out.push_str("    indirect [shape=ellipse];\n");
+        }
+        for block in self.blocks.values() {
+            for succ in &block.succs {
+                out.push_str(&format!("    b{} -> b{};\n", block.start, succ));
+            }
+            if block.indirect {
+                out.push_str(&format!("    b{} -> indirect [style=dashed];\n", block.start));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn report(&self) -> String {
+        let instrs: usize = self.blocks.values().map(|block| block.instrs.len()).sum();
+        let edges: usize = self.blocks.values().map(|block| block.succs.len()).sum();
+        let mut out = format!("{} instructions in {} blocks, {} edges\n",
+                              instrs, self.blocks.len(), edges);
+        let indirect: Vec<String> = self.indirect_jumps().iter().map(|pos| pos.to_string()).collect();
+        out.push_str(&format!("indirect jumps at: {}\n", indirect.join(" ")));
+        out.push_str("writes to code:\n");
+        for write in &self.code_writes {
+            out.push_str(&format!("  {} writes [{}] in the instruction at {}\n",
+                                  write.writer, write.addr, write.instr));
+        }
+        out
+    }
+}
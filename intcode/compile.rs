@@ -0,0 +1,210 @@
+// Ahead-of-time compiler from an Intcode image to a Rust module.  The
+// module exports
+//
+//   pub fn interpret(mem: &mut Vec<i64>,
+//                    read_input: &mut dyn FnMut() -> i64,
+//                    write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError>
+//
+// with the same contract as intcode::interpret(), for `mem` holding the
+// compiled image.  Each instruction disasm::reachable() finds becomes one
+// arm of a `match pc` loop, with its operands compiled in as constants.
+// Words listed as variable are read from memory instead, so callers can
+// patch them, like day 2's noun and verb.
+//
+// Once the program writes over its own code, each compiled instruction
+// first checks that its words are unchanged.  On a mismatch, a jump to an
+// address that was not compiled, or any fault, the module hands the current
+// state to the interpreter, which carries on from there and reports errors
+// exactly as interpret() does.
+//
+// The module refers to the virtual machine as `crate::intcode`.
+
+use std::fmt::Write;
+use super::{AddrMode, Opcode};
+use super::disasm::reachable;
+
+const PRELUDE: &str = "\
+// Generated by intcode::compile; do not edit.
+
+#![allow(unused_mut, unused_variables, unused_macros, dead_code, clippy::all)]
+
+use crate::intcode::{IntcodeError, Machine, Memory, DENSE_MAX_ADDR};
+
+// Leave the compiled code, so the interpreter runs the current instruction.
+macro_rules! get {
+    ($e:expr) => {
+        match $e {
+            Some(val) => val,
+            None => break,
+        }
+    };
+}
+
+fn load(mem: &Vec<i64>, addr: i64) -> Option<i64> {
+    if addr < 0 { None } else { Some(mem.read(addr as usize)) }
+}
+
+fn dest(addr: i64) -> Option<usize> {
+    if addr < 0 || addr as u64 > DENSE_MAX_ADDR as u64 { None } else { Some(addr as usize) }
+}
+
+fn store(mem: &mut Vec<i64>, addr: i64, val: i64, modified: &mut bool) -> Option<()> {
+    let addr = dest(addr)?;
+    mem.write(addr, val).ok()?;
+    if addr < IMAGE.len() && CODE[addr] != 0 && val != IMAGE[addr] {
+        *modified = true;
+    }
+    Some(())
+}
+
+fn jump(target: i64) -> Option<usize> {
+    if target < 0 { None } else { Some(target as usize) }
+}
+";
+
+const ENTRY: &str = "
+pub fn interpret(mem: &mut Vec<i64>,
+                 read_input: &mut dyn FnMut() -> i64,
+                 write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let mut pc = 0usize;
+    let mut rb = 0i64;
+    let mut modified = (0..IMAGE.len())
+        .any(|addr| CODE[addr] != 0 && mem.read(addr) != IMAGE[addr]);
+    loop {
+        match pc {
+";
+
+const EXIT: &str = "\
+            _ => break,
+        }
+    }
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    machine.pos = pc;
+    machine.relbase = rb;
+    let result = machine.run_with(read_input, write_output);
+    *mem = machine.mem;
+    result
+}
+";
+
+fn array(name: &str, ty: &str, words: &[i64]) -> String {
+    let mut out = format!("const {}: [{}; {}] = [\n", name, ty, words.len());
+    for chunk in words.chunks(16) {
+        let words: Vec<String> = chunk.iter().map(|w| w.to_string()).collect();
+        out.push_str(&format!("    {},\n", words.join(", ")));
+    }
+    out.push_str("];\n");
+    out
+}
+
+// A relative address, leaving the compiled code when it overflows.
+fn relative(word: &str) -> String {
+    format!("get!(rb.checked_add({}))", word)
+}
+
+pub fn compile(prog: &[i64], variable: &[usize]) -> String {
+    // the word at an address, as Rust source
+    let word = |addr: usize| if variable.contains(&addr) {
+        format!("mem.read({})", addr)
+    }
+    else {
+        prog[addr].to_string()
+    };
+
+    let mut is_code = vec![0; prog.len()];
+    let mut arms = String::new();
+    for (pos, instr) in reachable(prog).into_iter().enumerate() {
+        let instr = match instr {
+            Some(ref instr) if !variable.contains(&pos) => instr,
+            _ => continue,
+        };
+        let words: Vec<usize> = (pos..pos + instr.len())
+            .filter(|addr| !variable.contains(addr))
+            .collect();
+        for &addr in &words {
+            is_code[addr] = 1;
+        }
+        let read = |i: usize| match instr.params[i].0 {
+            AddrMode::IMMEDIATE => word(pos + 1 + i),
+            AddrMode::POSITION => format!("get!(load(mem, {}))", word(pos + 1 + i)),
+            AddrMode::RELATIVE => format!("get!(load(mem, {}))", relative(&word(pos + 1 + i))),
+        };
+        let write_addr = |i: usize| match instr.params[i].0 {
+            AddrMode::RELATIVE => relative(&word(pos + 1 + i)),
+            _ => word(pos + 1 + i),
+        };
+        let next = pos + instr.len();
+
+        let mut body = vec![];
+        match instr.opcode {
+            Opcode::ADD | Opcode::MUL => {
+                let op = if instr.opcode == Opcode::ADD {"checked_add"} else {"checked_mul"};
+                body.push(format!("let a: i64 = {};", read(0)));
+                body.push(format!("let val = get!(a.{}({}));", op, read(1)));
+                body.push(format!("get!(store(mem, {}, val, &mut modified));", write_addr(2)));
+                body.push(format!("pc = {};", next));
+            }
+            Opcode::LT | Opcode::EQ => {
+                let op = if instr.opcode == Opcode::LT {"<"} else {"=="};
+                body.push(format!("let a: i64 = {};", read(0)));
+                body.push(format!("let val = (a {} {}) as i64;", op, read(1)));
+                body.push(format!("get!(store(mem, {}, val, &mut modified));", write_addr(2)));
+                body.push(format!("pc = {};", next));
+            }
+            Opcode::IN => {
+                body.push(format!("get!(dest({}));", write_addr(0)));
+                body.push("let val = read_input();".to_string());
+                body.push(format!("get!(store(mem, {}, val, &mut modified));", write_addr(0)));
+                body.push(format!("pc = {};", next));
+            }
+            Opcode::OUT => {
+                body.push(format!("write_output({});", read(0)));
+                body.push(format!("pc = {};", next));
+            }
+            Opcode::JNZ | Opcode::JZ => {
+                let op = if instr.opcode == Opcode::JNZ {"!="} else {"=="};
+                body.push(format!("let a: i64 = {};", read(0)));
+                let target = match instr.jump_target() {
+                    Some(target) if target >= 0 && !variable.contains(&(pos + 2)) =>
+                        target.to_string(),
+                    _ => {
+                        // read even when not jumping, like the interpreter
+                        body.push(format!("let b: i64 = {};", read(1)));
+                        "get!(jump(b))".to_string()
+                    }
+                };
+                body.push(format!("pc = if a {} 0 {{ {} }} else {{ {} }};", op, target, next));
+            }
+            Opcode::ARB => {
+                body.push(format!("rb = get!(rb.checked_add({}));", read(0)));
+                body.push(format!("pc = {};", next));
+            }
+            Opcode::HLT => body.push("return Ok(());".to_string()),
+        }
+
+        writeln!(arms, "            // {}", instr).unwrap();
+        writeln!(arms, "            {} => {{", pos).unwrap();
+        let checks: Vec<String> = words.iter()
+            .map(|&addr| format!("mem.read({}) != {}", addr, prog[addr]))
+            .collect();
+        if !checks.is_empty() {
+            writeln!(arms, "                if modified && ({}) {{", checks.join(" || ")).unwrap();
+            writeln!(arms, "                    break;").unwrap();
+            writeln!(arms, "                }}").unwrap();
+        }
+        for line in body {
+            writeln!(arms, "                {}", line).unwrap();
+        }
+        writeln!(arms, "            }}").unwrap();
+    }
+
+    let mut out = PRELUDE.to_string();
+    out.push('\n');
+    out.push_str(&array("IMAGE", "i64", prog));
+    out.push('\n');
+    out.push_str(&array("CODE", "u8", &is_code));
+    out.push_str(ENTRY);
+    out.push_str(&arms);
+    out.push_str(EXIT);
+    out
+}
@@ -0,0 +1,218 @@
+// Interactive debugger for the Intcode machine.
+
+use std::collections::BTreeSet;
+use std::io;
+use std::io::prelude::*;
+use super::{Machine, Memory, Event, IntcodeError};
+use super::disasm::Instruction;
+use super::snapshot;
+
+const HELP: &str = "\
+commands:
+  s, step [N]           execute N instructions (default 1)
+  c, continue           run until a breakpoint, watchpoint, input wait or halt
+  b, break [ADDR]       set a breakpoint at ADDR, or list breakpoints
+  w, watch [ADDR]       stop after writes to ADDR, or list watchpoints
+  d, delete ADDR        remove the breakpoint and watchpoint at ADDR
+  x, dump START [END]   show memory from START up to END (default START+8)
+  r, regs               show pos, relbase and queued input
+  l, list               show the current instruction
+  i, input VAL...       queue input values
+  set ADDR VAL          store VAL at ADDR
+  save PATH             write a snapshot of the machine to PATH
+  load PATH             replace the machine with the snapshot in PATH
+  q, quit               leave the debugger";
+
+enum Stop {
+    Steps,
+    Breakpoint,
+    Watchpoint(usize),
+    NeedInput,
+    Halted,
+    Fault(IntcodeError),
+}
+
+pub struct Debugger {
+    pub machine: Machine,
+    pub breakpoints: BTreeSet<usize>,
+    pub watchpoints: BTreeSet<usize>,
+}
+
+fn parse_num<T: std::str::FromStr>(arg: Option<&&str>, what: &str) -> Result<T, String> {
+    let arg = arg.ok_or_else(|| format!("missing {}", what))?;
+    arg.parse().map_err(|_| format!("invalid {} {:?}", what, arg))
+}
+
+impl Debugger {
+    pub fn new(machine: Machine) -> Debugger {
+        Debugger { machine, breakpoints: BTreeSet::new(), watchpoints: BTreeSet::new() }
+    }
+
+    fn current(&self) -> String {
+        let pos = self.machine.pos;
+        let words: Vec<i64> = (pos..pos + 4).map(|a| self.machine.mem.read(a)).collect();
+        match Instruction::decode(&words, 0) {
+            Some(instr) => format!("{:>5}: {}", self.machine.pos, instr),
+            None => format!("{:>5}: <invalid instruction>", self.machine.pos),
+        }
+    }
+
+    // Execute one instruction, reporting why execution should stop, if it
+    // should.
+    fn step_one(&mut self, out: &mut dyn Write) -> io::Result<Option<Stop>> {
+        let write_addr = self.machine.write_addr();
+        match self.machine.step() {
+            Ok(Some(Event::Output(val))) => writeln!(out, "output: {}", val)?,
+            Ok(Some(Event::NeedInput)) => return Ok(Some(Stop::NeedInput)),
+            Ok(Some(Event::Halted)) => return Ok(Some(Stop::Halted)),
+            Ok(None) => (),
+            Err(e) => return Ok(Some(Stop::Fault(e))),
+        }
+        if let Some(addr) = write_addr {
+            if addr >= 0 && self.watchpoints.contains(&(addr as usize)) {
+                return Ok(Some(Stop::Watchpoint(addr as usize)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn report(&self, stop: Stop, out: &mut dyn Write) -> io::Result<()> {
+        match stop {
+            Stop::Steps => (),
+            Stop::Breakpoint => writeln!(out, "breakpoint")?,
+            Stop::Watchpoint(addr) =>
+                writeln!(out, "watchpoint: [{}] = {}", addr,
+                         self.machine.mem.read(addr))?,
+            Stop::NeedInput => writeln!(out, "waiting for input")?,
+            Stop::Halted => writeln!(out, "halted")?,
+            Stop::Fault(e) => writeln!(out, "error: {}", e)?,
+        }
+        writeln!(out, "{}", self.current())
+    }
+
+    fn run_command(&mut self, words: &[&str], out: &mut dyn Write)
+                   -> Result<bool, String> {
+        let io_err = |e: io::Error| e.to_string();
+        match words[0] {
+            "s" | "step" => {
+                let count: usize = if words.len() > 1 { parse_num(words.get(1), "count")? } else { 1 };
+                let mut stop = Stop::Steps;
+                for _ in 0..count {
+                    if let Some(s) = self.step_one(out).map_err(io_err)? {
+                        stop = s;
+                        break;
+                    }
+                }
+                self.report(stop, out).map_err(io_err)?;
+            }
+            "c" | "continue" => {
+                let stop = loop {
+                    if let Some(s) = self.step_one(out).map_err(io_err)? {
+                        break s;
+                    }
+                    if self.breakpoints.contains(&self.machine.pos) {
+                        break Stop::Breakpoint;
+                    }
+                };
+                self.report(stop, out).map_err(io_err)?;
+            }
+            "b" | "break" | "w" | "watch" => {
+                let set = if words[0].starts_with('b') {
+                    &mut self.breakpoints
+                }
+                else {
+                    &mut self.watchpoints
+                };
+                if words.len() > 1 {
+                    set.insert(parse_num(words.get(1), "address")?);
+                }
+                else {
+                    let addrs: Vec<String> = set.iter().map(|a| a.to_string()).collect();
+                    writeln!(out, "{}", addrs.join(" ")).map_err(io_err)?;
+                }
+            }
+            "d" | "delete" => {
+                let addr = parse_num(words.get(1), "address")?;
+                self.breakpoints.remove(&addr);
+                self.watchpoints.remove(&addr);
+            }
+            "x" | "dump" => {
+                let start: usize = parse_num(words.get(1), "address")?;
+                let end: usize = if words.len() > 2 { parse_num(words.get(2), "address")? } else { start + 8 };
+                let mut addr = start;
+                while addr < end {
+                    let line_end = std::cmp::min(addr + 8, end);
+                    let vals: Vec<String> = (addr..line_end)
+                        .map(|a| self.machine.mem.read(a).to_string())
+                        .collect();
+                    writeln!(out, "{:>5}: {}", addr, vals.join(" ")).map_err(io_err)?;
+                    addr = line_end;
+                }
+            }
+            "r" | "regs" => {
+                let input: Vec<String> = self.machine.input.iter().map(|v| v.to_string()).collect();
+                writeln!(out, "pos {} relbase {} input [{}]",
+                         self.machine.pos, self.machine.relbase, input.join(", "))
+                    .map_err(io_err)?;
+            }
+            "l" | "list" => writeln!(out, "{}", self.current()).map_err(io_err)?,
+            "i" | "input" => {
+                if words.len() < 2 {
+                    return Err("missing value".to_string());
+                }
+                for i in 1..words.len() {
+                    let val = parse_num(words.get(i), "value")?;
+                    self.machine.push_input(val);
+                }
+            }
+            "set" => {
+                let addr: usize = parse_num(words.get(1), "address")?;
+                let val = parse_num(words.get(2), "value")?;
+                self.machine.mem.write(addr, val)
+                    .map_err(|_| format!("address {} beyond the memory limit", addr))?;
+            }
+            "save" => {
+                let path = words.get(1).ok_or("missing path")?;
+                snapshot::save_file(&self.machine, path).map_err(io_err)?;
+            }
+            "load" => {
+                let path = words.get(1).ok_or("missing path")?;
+                self.machine = snapshot::load_file(path).map_err(|e| e.to_string())?;
+                writeln!(out, "{}", self.current()).map_err(io_err)?;
+            }
+            "h" | "help" => writeln!(out, "{}", HELP).map_err(io_err)?,
+            "q" | "quit" => return Ok(false),
+            other => return Err(format!("unknown command {:?}, try help", other)),
+        }
+        Ok(true)
+    }
+
+    // Execute one command line.  Returns false when the user quits.
+    pub fn command(&mut self, line: &str, out: &mut dyn Write) -> io::Result<bool> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            return Ok(true);
+        }
+        match self.run_command(&words, out) {
+            Ok(more) => Ok(more),
+            Err(msg) => {
+                writeln!(out, "{}", msg)?;
+                Ok(true)
+            }
+        }
+    }
+
+    pub fn repl(&mut self, input: impl BufRead, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", self.current())?;
+        write!(out, "(icdb) ")?;
+        out.flush()?;
+        for line in input.lines() {
+            if !self.command(&line?, out)? {
+                return Ok(());
+            }
+            write!(out, "(icdb) ")?;
+            out.flush()?;
+        }
+        writeln!(out)
+    }
+}
@@ -0,0 +1,198 @@
+// I/O devices for a machine.  The program's input instructions read from
+// its device and its output instructions write to it, so the protocol a
+// program speaks can be written once as a device instead of being parsed
+// by hand in each pair of callbacks.  Ready-made devices:
+//
+//   Queue      fixed input, with the output collected
+//   Framer     output grouped into fixed-size frames, like 13's triplets
+//   Ascii      input as lines of text, output as lines and other values
+//   Tee        logs every value passed through another device
+//
+// Devices speak the machine's word type.  Ascii and Tee, whose text and
+// recordings are i64, only run on i64 machines.
+
+use std::collections::VecDeque;
+use super::{Machine, Memory, Word, Event, ErrorKind, IntcodeError};
+use super::replay::Io;
+
+pub trait Device<W: Word = i64> {
+    // The next input word, or None when the device has none, which stops
+    // the run with InputExhausted.
+    fn read(&mut self) -> Option<W>;
+    fn write(&mut self, val: W);
+    // Called once when the program halts.
+    fn halted(&mut self) {}
+}
+
+// Run to completion with `device` attached.
+pub fn run<M: Memory>(machine: &mut Machine<M>, device: &mut dyn Device<M::Word>)
+                      -> Result<(), IntcodeError<M::Word>> {
+    loop {
+        match machine.run()? {
+            Event::NeedInput => match device.read() {
+                Some(val) => machine.push_input(val),
+                None => return Err(IntcodeError {
+                    pc: machine.pos,
+                    instruction: machine.mem.read(machine.pos),
+                    kind: ErrorKind::InputExhausted,
+                }),
+            },
+            Event::Output(val) => device.write(val),
+            Event::Halted => {
+                device.halted();
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Queue<W = i64> {
+    pub input: VecDeque<W>,
+    pub output: Vec<W>,
+}
+
+impl<W: Word> Queue<W> {
+    pub fn new(input: &[W]) -> Queue<W> {
+        Queue { input: input.iter().cloned().collect(), output: vec![] }
+    }
+}
+
+impl<W: Word> Device<W> for Queue<W> {
+    fn read(&mut self) -> Option<W> {
+        self.input.pop_front()
+    }
+
+    fn write(&mut self, val: W) {
+        self.output.push(val);
+    }
+}
+
+// The two sides of a protocol whose output comes in frames.
+pub trait FrameHandler<W: Word = i64> {
+    fn read(&mut self) -> Option<W>;
+    fn frame(&mut self, frame: &[W]);
+}
+
+pub struct Framer<H, W = i64> {
+    pub handler: H,
+    size: usize,
+    pending: Vec<W>,
+}
+
+impl<W: Word, H: FrameHandler<W>> Framer<H, W> {
+    pub fn new(size: usize, handler: H) -> Framer<H, W> {
+        Framer { handler, size, pending: Vec::with_capacity(size) }
+    }
+
+    // Output of a frame not yet complete.
+    pub fn pending(&self) -> &[W] {
+        &self.pending
+    }
+}
+
+impl<W: Word, H: FrameHandler<W>> Device<W> for Framer<H, W> {
+    fn read(&mut self) -> Option<W> {
+        self.handler.read()
+    }
+
+    fn write(&mut self, val: W) {
+        self.pending.push(val);
+        if self.pending.len() == self.size {
+            self.handler.frame(&self.pending);
+            self.pending.clear();
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Text {
+    // a line without its newline
+    Line(String),
+    // text written before the program asked for input or halted, without
+    // a newline after it
+    Partial(String),
+    // an output outside the ASCII range
+    Value(i64),
+}
+
+// Text protocol: inputs are lines from `read_line`, fed as character codes
+// with a newline after each, and outputs in 0..=127 are characters.
+pub struct Ascii<R, W> {
+    read_line: R,
+    on_text: W,
+    input: VecDeque<i64>,
+    line: String,
+}
+
+impl<R: FnMut() -> Option<String>, W: FnMut(Text)> Ascii<R, W> {
+    pub fn new(read_line: R, on_text: W) -> Ascii<R, W> {
+        Ascii { read_line, on_text, input: VecDeque::new(), line: String::new() }
+    }
+
+    fn flush(&mut self) {
+        if !self.line.is_empty() {
+            let line = std::mem::take(&mut self.line);
+            (self.on_text)(Text::Partial(line));
+        }
+    }
+}
+
+impl<R: FnMut() -> Option<String>, W: FnMut(Text)> Device for Ascii<R, W> {
+    fn read(&mut self) -> Option<i64> {
+        if self.input.is_empty() {
+            self.flush();
+            let line = (self.read_line)()?;
+            self.input.extend(line.bytes().map(|b| b as i64));
+            self.input.push_back('\n' as i64);
+        }
+        self.input.pop_front()
+    }
+
+    fn write(&mut self, val: i64) {
+        match val {
+            10 => {
+                let line = std::mem::take(&mut self.line);
+                (self.on_text)(Text::Line(line));
+            }
+            0..=127 => self.line.push(val as u8 as char),
+            _ => {
+                self.flush();
+                (self.on_text)(Text::Value(val));
+            }
+        }
+    }
+
+    fn halted(&mut self) {
+        self.flush();
+    }
+}
+
+// Passes everything through to `inner`, logging it on the way.
+pub struct Tee<D, L> {
+    pub inner: D,
+    log: L,
+}
+
+impl<D: Device, L: FnMut(Io)> Tee<D, L> {
+    pub fn new(inner: D, log: L) -> Tee<D, L> {
+        Tee { inner, log }
+    }
+}
+
+impl<D: Device, L: FnMut(Io)> Device for Tee<D, L> {
+    fn read(&mut self) -> Option<i64> {
+        let val = self.inner.read()?;
+        (self.log)(Io::In(val));
+        Some(val)
+    }
+
+    fn write(&mut self, val: i64) {
+        (self.log)(Io::Out(val));
+        self.inner.write(val);
+    }
+
+    fn halted(&mut self) {
+        self.inner.halted();
+    }
+}
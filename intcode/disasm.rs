@@ -0,0 +1,154 @@
+// Intcode disassembler.  Instructions are decoded by decode_param_modes(),
+// the decoder the interpreter itself runs on, and only words reachable from
+// address 0 are shown as code; everything else is listed as data.
+
+use std::collections::HashSet;
+use std::fmt;
+use super::{AddrMode, Opcode, decode_param_modes};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub params: Vec<(AddrMode, i64)>,
+}
+
+impl Instruction {
+    // Decode the instruction at `pos`, or None if the word there is not a
+    // valid instruction or its parameters run past the end of memory.
+    pub fn decode(mem: &[i64], pos: usize) -> Option<Instruction> {
+        let (opcode, modes) = decode_param_modes(*mem.get(pos)?).ok()?;
+        if pos + 1 + modes.len() > mem.len() {
+            return None;
+        }
+        let params = modes.into_iter()
+            .zip(&mem[pos + 1..])
+            .map(|(mode, &val)| (mode, val))
+            .collect();
+        Some(Instruction { opcode, params })
+    }
+
+    pub fn len(&self) -> usize {
+        1 + self.params.len()
+    }
+
+    // Statically known jump target: the second parameter of jnz/jz when it
+    // is immediate.
+    pub fn jump_target(&self) -> Option<i64> {
+        match self.opcode {
+            Opcode::JNZ | Opcode::JZ => match self.params[1] {
+                (AddrMode::IMMEDIATE, target) => Some(target),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // Whether execution can continue with the following instruction.
+    pub fn falls_through(&self) -> bool {
+        match (self.opcode, self.params.first()) {
+            (Opcode::HLT, _) => false,
+            (Opcode::JNZ, Some(&(AddrMode::IMMEDIATE, cond))) => cond == 0,
+            (Opcode::JZ, Some(&(AddrMode::IMMEDIATE, cond))) => cond != 0,
+            _ => true,
+        }
+    }
+
+    // Constant that an add or mul with only immediate operands stores.
+    // Calls push their return address this way before jumping.
+    pub fn stored_constant(&self) -> Option<i64> {
+        match (self.opcode, self.params.first(), self.params.get(1)) {
+            (Opcode::ADD, Some(&(AddrMode::IMMEDIATE, a)), Some(&(AddrMode::IMMEDIATE, b))) =>
+                a.checked_add(b),
+            (Opcode::MUL, Some(&(AddrMode::IMMEDIATE, a)), Some(&(AddrMode::IMMEDIATE, b))) =>
+                a.checked_mul(b),
+            _ => None,
+        }
+    }
+}
+
+pub fn format_param(mode: AddrMode, val: i64) -> String {
+    match mode {
+        AddrMode::POSITION => format!("[{}]", val),
+        AddrMode::IMMEDIATE => format!("#{}", val),
+        AddrMode::RELATIVE if val < 0 => format!("rb{}", val),
+        AddrMode::RELATIVE => format!("rb+{}", val),
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.opcode.mnemonic())?;
+        for (i, &(mode, val)) in self.params.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 {" "} else {", "}, format_param(mode, val))?;
+        }
+        Ok(())
+    }
+}
+
+// Find the instructions reachable from address 0 by following fall-through
+// and immediate jump targets.  The word after an unconditional jump is also
+// treated as reachable if some instruction stores its address, which is how
+// programs set up the return address of a call.  Returns, for each address,
+// the instruction starting there, if it is reachable.
+pub fn reachable(mem: &[i64]) -> Vec<Option<Instruction>> {
+    let mut code: Vec<Option<Instruction>> = vec![None; mem.len()];
+    let mut constants = HashSet::new();
+    let mut return_sites: Vec<usize> = vec![];
+    let mut todo = vec![0usize];
+    loop {
+        while let Some(pos) = todo.pop() {
+            if pos >= mem.len() || code[pos].is_some() {
+                continue;
+            }
+            let instr = match Instruction::decode(mem, pos) {
+                Some(instr) => instr,
+                None => continue,
+            };
+            if let Some(target) = instr.jump_target() {
+                if target >= 0 {
+                    todo.push(target as usize);
+                }
+            }
+            if let Some(val) = instr.stored_constant() {
+                constants.insert(val);
+            }
+            if instr.falls_through() {
+                todo.push(pos + instr.len());
+            }
+            else if instr.opcode != Opcode::HLT {
+                return_sites.push(pos + instr.len());
+            }
+            code[pos] = Some(instr);
+        }
+        let (found, rest) = return_sites.into_iter()
+            .partition(|&pos| constants.contains(&(pos as i64)));
+        todo = found;
+        return_sites = rest;
+        if todo.is_empty() {
+            break;
+        }
+    }
+    code
+}
+
+const DATA_PER_LINE: usize = 8;
+
+pub fn disassemble(mem: &[i64]) -> String {
+    let code = reachable(mem);
+    let mut out = String::new();
+    let mut pos = 0;
+    while pos < mem.len() {
+        if let Some(ref instr) = code[pos] {
+            out.push_str(&format!("{:>5}: {}\n", pos, instr));
+            pos += instr.len();
+            continue;
+        }
+        let start = pos;
+        while pos < mem.len() && code[pos].is_none() && pos - start < DATA_PER_LINE {
+            pos += 1;
+        }
+        let words: Vec<String> = mem[start..pos].iter().map(|v| v.to_string()).collect();
+        out.push_str(&format!("{:>5}: data {}\n", start, words.join(", ")));
+    }
+    out
+}
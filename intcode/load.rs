@@ -0,0 +1,101 @@
+// Loading programs from their comma-separated text.  Commas and line breaks
+// both separate numbers, so trailing commas and blank lines are harmless,
+// `#` starts a comment running to the end of its line, and `\r` from files
+// edited on Windows is ignored.  A bad number is reported with where it is.
+// Images in binary's format are recognised and loaded too.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use super::binary::{self, BinaryError, Contents};
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    // `index` counts numbers from 0; `line` and `column` count from 1
+    BadNumber { index: usize, line: usize, column: usize, token: String },
+    Binary(BinaryError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::Io(ref err) => write!(f, "{}", err),
+            LoadError::BadNumber { index, line, column, ref token } =>
+                write!(f, "line {}, column {}: number {} is invalid: {:?}",
+                       line, column, index, token),
+            LoadError::Binary(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> LoadError {
+        LoadError::Io(err)
+    }
+}
+
+pub fn parse(text: &str) -> Result<Vec<i64>, LoadError> {
+    let mut ret = vec![];
+    for (line_index, line) in text.lines().enumerate() {
+        let line = match line.find('#') {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        let mut start = 0;
+        for tok in line.split(',') {
+            let column = start + tok.len() - tok.trim_start().len();
+            start += tok.len() + 1;
+            let tok = tok.trim();
+            if tok.is_empty() {
+                continue;
+            }
+            match tok.parse() {
+                Ok(val) => ret.push(val),
+                Err(_) => return Err(LoadError::BadNumber {
+                    index: ret.len(),
+                    line: line_index + 1,
+                    column: line[..column].chars().count() + 1,
+                    token: tok.to_string(),
+                }),
+            }
+        }
+    }
+    Ok(ret)
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Result<Vec<i64>, LoadError> {
+    if binary::is_binary(bytes) {
+        return match binary::from_bytes(bytes) {
+            Ok(Contents::Image(words)) => Ok(words),
+            Ok(Contents::Snapshot(_)) => Err(LoadError::Binary(BinaryError {
+                offset: 0,
+                msg: "a snapshot, not a program".to_string(),
+            })),
+            Err(err) => Err(LoadError::Binary(err)),
+        };
+    }
+    let text = std::str::from_utf8(bytes)
+        .map_err(|err| LoadError::Io(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+    parse(text)
+}
+
+pub fn from_reader(input: &mut dyn Read) -> Result<Vec<i64>, LoadError> {
+    let mut bytes = vec![];
+    input.read_to_end(&mut bytes)?;
+    from_bytes(&bytes)
+}
+
+pub fn from_file(path: &str) -> Result<Vec<i64>, LoadError> {
+    from_bytes(&fs::read(path)?)
+}
+
+pub fn from_stdin() -> Result<Vec<i64>, LoadError> {
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    from_reader(&mut stdin)
+}
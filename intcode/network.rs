@@ -0,0 +1,117 @@
+// Network of Intcode machines wired output to input.  Every output of a
+// node is delivered to the input of each node it is connected to, so a
+// chain, a ring, fan-out and broadcast are all just sets of links.  Outputs
+// of tapped nodes are also collected as the result of the run.
+//
+//   let mut net = Network::new();
+//   let a = net.add(Machine::new(&prog), &[1]);
+//   let b = net.add(Machine::new(&prog), &[2]);
+//   net.ring(&[a, b]);
+//   net.push_input(a, 0);
+//   net.tap(b);
+//   let outputs = net.run()?;
+//
+// Nodes take turns on a Scheduler.  The run ends when every node has halted,
+// and is a deadlock if some node is still waiting for input that no other
+// node can provide.
+
+use std::error::Error;
+use std::fmt;
+use super::{Machine, IntcodeError};
+use super::scheduler::Scheduler;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    // the node and its error
+    Fault(usize, IntcodeError),
+    // nodes waiting for input when no node can run
+    Deadlock(Vec<usize>),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NetworkError::Fault(node, ref err) => write!(f, "node {}: {}", node, err),
+            NetworkError::Deadlock(ref nodes) => {
+                let nodes: Vec<String> = nodes.iter().map(|n| n.to_string()).collect();
+                write!(f, "deadlock, nodes {} waiting for input", nodes.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for NetworkError {}
+
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    pub sched: Scheduler,
+    // destinations of each node's outputs
+    links: Vec<Vec<usize>>,
+    tapped: Vec<bool>,
+}
+
+impl Network {
+    pub fn new() -> Network {
+        Network::default()
+    }
+
+    // Add a node with some initial input, returning its index.
+    pub fn add(&mut self, mut machine: Machine, inputs: &[i64]) -> usize {
+        machine.input.extend(inputs);
+        self.links.push(vec![]);
+        self.tapped.push(false);
+        self.sched.add(machine)
+    }
+
+    pub fn push_input(&mut self, node: usize, val: i64) {
+        self.sched.machines[node].push_input(val);
+    }
+
+    pub fn connect(&mut self, from: usize, to: usize) {
+        self.links[from].push(to);
+    }
+
+    // Connect each node to the next.
+    pub fn chain(&mut self, nodes: &[usize]) {
+        for pair in nodes.windows(2) {
+            self.connect(pair[0], pair[1]);
+        }
+    }
+
+    // A chain with the last node connected back to the first.
+    pub fn ring(&mut self, nodes: &[usize]) {
+        self.chain(nodes);
+        if let (Some(&first), Some(&last)) = (nodes.first(), nodes.last()) {
+            self.connect(last, first);
+        }
+    }
+
+    pub fn broadcast(&mut self, from: usize, to: &[usize]) {
+        for &node in to {
+            self.connect(from, node);
+        }
+    }
+
+    pub fn tap(&mut self, node: usize) {
+        self.tapped[node] = true;
+    }
+
+    // Run until every node halts, returning the outputs of the tapped nodes
+    // in the order they were produced.
+    pub fn run(&mut self) -> Result<Vec<i64>, NetworkError> {
+        let (links, taps) = (&self.links, &self.tapped);
+        let mut tapped = vec![];
+        let report = self.sched.run(&mut |machines, node, val| {
+            if taps[node] {
+                tapped.push(val);
+            }
+            for &dest in &links[node] {
+                machines[dest].push_input(val);
+            }
+        }).map_err(|(node, err)| NetworkError::Fault(node, err))?;
+        if report.is_deadlock() {
+            return Err(NetworkError::Deadlock(report.stuck));
+        }
+        Ok(tapped)
+    }
+}
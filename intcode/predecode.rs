@@ -0,0 +1,183 @@
+// Intcode engine that decodes the program image once up front and caches
+// each instruction's opcode and parameter modes by address, so the hot loop
+// does no mode-digit arithmetic.  A cache entry remembers the instruction
+// word it was decoded from and is only used while memory still holds that
+// word, so self-modifying code runs as it does under Machine::step().
+// Anything the cache does not cover, faults and code outside the program
+// image, is handed to Machine::step() itself, so results and errors match
+// the reference interpreter.
+//
+// Decoding costs about as much as running each instruction once, so for
+// many short runs of one program, decode it once and clone the engine for
+// each run.  Clones share the cache.
+
+use std::rc::Rc;
+use super::{AddrMode, Opcode, Machine, Memory, PagedMemory, Event, IntcodeError,
+            decode_instruction};
+
+#[derive(Debug, Copy, Clone)]
+struct Op {
+    instruction: i64,
+    opcode: Opcode,
+    modes: [AddrMode; 3],
+}
+
+#[derive(Debug, Clone)]
+pub struct Predecoded<M: Memory = PagedMemory> {
+    pub machine: Machine<M>,
+    // decoded instruction by address, for the program image
+    cache: Rc<Vec<Option<Op>>>,
+}
+
+impl Predecoded {
+    pub fn new(prog: &[i64]) -> Predecoded {
+        Predecoded::from_machine(Machine::new(prog))
+    }
+}
+
+fn decode(instruction: i64) -> Option<Op> {
+    let (opcode, modes) = decode_instruction(instruction).ok()?;
+    Some(Op { instruction, opcode, modes })
+}
+
+impl<M: Memory<Word = i64>> Predecoded<M> {
+    pub fn from_machine(machine: Machine<M>) -> Predecoded<M> {
+        let cache = machine.mem.segments()[0].1.iter().map(|&word| decode(word)).collect();
+        Predecoded { machine, cache: Rc::new(cache) }
+    }
+
+    pub fn push_input(&mut self, val: i64) {
+        self.machine.push_input(val);
+    }
+
+    fn addr(&self, mode: AddrMode, val: i64) -> Option<usize> {
+        let addr = match mode {
+            AddrMode::RELATIVE => self.machine.relbase.checked_add(val)?,
+            _ => val,
+        };
+        if addr < 0 { None } else { Some(addr as usize) }
+    }
+
+    fn read(&self, op: &Op, i: usize) -> Option<i64> {
+        let val = self.machine.mem.read(self.machine.pos + 1 + i);
+        match op.modes[i] {
+            AddrMode::IMMEDIATE => Some(val),
+            mode => self.addr(mode, val).map(|addr| self.machine.mem.read(addr)),
+        }
+    }
+
+    fn write(&mut self, op: &Op, i: usize, val: i64) -> Option<()> {
+        let raw = self.machine.mem.read(self.machine.pos + 1 + i);
+        let addr = self.addr(op.modes[i], raw)?;
+        self.machine.mem.write(addr, val).ok()
+    }
+
+    // Execute a decoded instruction at the current position.  Returns None,
+    // with the machine unchanged, if the instruction faults.
+    fn execute(&mut self, op: &Op) -> Option<Option<Event>> {
+        let next = self.machine.pos + 1 + op.opcode.param_count();
+        match op.opcode {
+            Opcode::ADD => {
+                let val = self.read(op, 0)?.checked_add(self.read(op, 1)?)?;
+                self.write(op, 2, val)?;
+            }
+            Opcode::MUL => {
+                let val = self.read(op, 0)?.checked_mul(self.read(op, 1)?)?;
+                self.write(op, 2, val)?;
+            }
+            Opcode::IN => {
+                let val = match self.machine.input.pop_front() {
+                    Some(val) => val,
+                    None => return Some(Some(Event::NeedInput)),
+                };
+                if self.write(op, 0, val).is_none() {
+                    self.machine.input.push_front(val);
+                    return None;
+                }
+            }
+            Opcode::OUT => {
+                let val = self.read(op, 0)?;
+                self.machine.pos = next;
+                return Some(Some(Event::Output(val)));
+            }
+            Opcode::JNZ | Opcode::JZ => {
+                let cond = self.read(op, 0)?;
+                let target = self.read(op, 1)?;
+                if (cond != 0) == (op.opcode == Opcode::JNZ) {
+                    if target < 0 {
+                        return None;
+                    }
+                    self.machine.pos = target as usize;
+                    return Some(None);
+                }
+            }
+            Opcode::LT => {
+                let val = if self.read(op, 0)? < self.read(op, 1)? {1} else {0};
+                self.write(op, 2, val)?;
+            }
+            Opcode::EQ => {
+                let val = if self.read(op, 0)? == self.read(op, 1)? {1} else {0};
+                self.write(op, 2, val)?;
+            }
+            Opcode::ARB => {
+                self.machine.relbase = self.machine.relbase.checked_add(self.read(op, 0)?)?;
+            }
+            Opcode::HLT => return Some(Some(Event::Halted)),
+        }
+        self.machine.pos = next;
+        Some(None)
+    }
+
+    // Same as Machine::step().
+    pub fn step(&mut self) -> Result<Option<Event>, IntcodeError> {
+        let pos = self.machine.pos;
+        let instruction = self.machine.mem.read(pos);
+        let op = match self.cache.get(pos) {
+            Some(&Some(op)) if op.instruction == instruction => op,
+            // overwritten since it was decoded
+            Some(_) => match decode(instruction) {
+                Some(op) => {
+                    Rc::make_mut(&mut self.cache)[pos] = Some(op);
+                    op
+                }
+                None => return self.machine.step(),
+            },
+            None => return self.machine.step(),
+        };
+        match self.execute(&op) {
+            Some(event) => Ok(event),
+            None => self.machine.step(),
+        }
+    }
+
+    pub fn run(&mut self) -> Result<Event, IntcodeError> {
+        loop {
+            if let Some(event) = self.step()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    pub fn run_with(&mut self,
+                    read_input: &mut dyn FnMut() -> i64,
+                    write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+        loop {
+            match self.run()? {
+                Event::NeedInput => self.push_input(read_input()),
+                Event::Output(val) => write_output(val),
+                Event::Halted => return Ok(()),
+            }
+        }
+    }
+}
+
+// Same as interpret(), on the pre-decoding engine.
+pub fn interpret_predecoded(mem: &mut Vec<i64>,
+                            read_input: &mut dyn FnMut() -> i64,
+                            write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+    let machine = Machine::with_memory(std::mem::take(mem));
+    let mut engine = Predecoded::from_machine(machine);
+    let result = engine.run_with(read_input, write_output);
+    *mem = engine.machine.mem;
+    result
+}
@@ -0,0 +1,105 @@
+// Execution profile of an Intcode run: how often each opcode and each
+// instruction address executes, and how much I/O the program does.
+
+use std::collections::HashMap;
+use super::{Opcode, Machine, Memory, Event, IntcodeError};
+use super::disasm::Instruction;
+
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    pub total: u64,
+    pub inputs: u64,
+    pub outputs: u64,
+    pub by_opcode: HashMap<Opcode, u64>,
+    pub by_addr: HashMap<usize, u64>,
+}
+
+fn percent(count: u64, total: u64) -> f64 {
+    if total == 0 { 0. } else { count as f64 * 100. / total as f64 }
+}
+
+impl Profile {
+    pub fn new() -> Profile {
+        Profile::default()
+    }
+
+    // Like Machine::step(), but counts the executed instruction.
+    pub fn step<M: Memory<Word = i64>>(&mut self, machine: &mut Machine<M>)
+                           -> Result<Option<Event>, IntcodeError> {
+        let pos = machine.pos;
+        let opcode = Opcode::decode(machine.mem.read(pos));
+        let event = machine.step()?;
+        match event {
+            Some(Event::NeedInput) | Some(Event::Halted) => return Ok(event),
+            Some(Event::Output(_)) => self.outputs += 1,
+            None => (),
+        }
+        // the step succeeded, so the opcode was valid
+        let opcode = opcode.unwrap();
+        if opcode == Opcode::IN {
+            self.inputs += 1;
+        }
+        self.total += 1;
+        *self.by_opcode.entry(opcode).or_insert(0) += 1;
+        *self.by_addr.entry(pos).or_insert(0) += 1;
+        Ok(event)
+    }
+
+    pub fn run<M: Memory<Word = i64>>(&mut self, machine: &mut Machine<M>) -> Result<Event, IntcodeError> {
+        loop {
+            if let Some(event) = self.step(machine)? {
+                return Ok(event);
+            }
+        }
+    }
+
+    pub fn run_with<M: Memory<Word = i64>>(&mut self, machine: &mut Machine<M>,
+                               read_input: &mut dyn FnMut() -> i64,
+                               write_output: &mut dyn FnMut(i64)) -> Result<(), IntcodeError> {
+        loop {
+            match self.run(machine)? {
+                Event::NeedInput => machine.push_input(read_input()),
+                Event::Output(val) => write_output(val),
+                Event::Halted => return Ok(()),
+            }
+        }
+    }
+
+    // Summary with the `top` most executed addresses, disassembled from
+    // `prog` where it holds a valid instruction at that address.
+    pub fn report(&self, prog: &[i64], top: usize) -> String {
+        let mut out = format!("instructions executed: {}\ninputs: {}  outputs: {}\n\n",
+                              self.total, self.inputs, self.outputs);
+        out.push_str("by opcode:\n");
+        let mut opcodes: Vec<(&Opcode, &u64)> = self.by_opcode.iter().collect();
+        opcodes.sort_by_key(|&(op, &count)| (std::cmp::Reverse(count), op.code()));
+        for (op, &count) in opcodes {
+            out.push_str(&format!("  {:<4} {:>12} {:>6.2}%\n",
+                                  op.mnemonic(), count, percent(count, self.total)));
+        }
+        out.push_str("\nhot spots:\n");
+        let mut addrs: Vec<(&usize, &u64)> = self.by_addr.iter().collect();
+        addrs.sort_by_key(|&(&addr, &count)| (std::cmp::Reverse(count), addr));
+        for (&addr, &count) in addrs.into_iter().take(top) {
+            let instr = match Instruction::decode(prog, addr) {
+                Some(instr) => instr.to_string(),
+                None => "?".to_string(),
+            };
+            out.push_str(&format!("  {:>5} {:>12} {:>6.2}%  {}\n",
+                                  addr, count, percent(count, self.total), instr));
+        }
+        out
+    }
+}
+
+// Run the program in `mem` to completion like interpret(), returning its
+// profile.
+pub fn interpret_profiled(mem: &mut Vec<i64>,
+                          read_input: &mut dyn FnMut() -> i64,
+                          write_output: &mut dyn FnMut(i64)) -> Result<Profile, IntcodeError> {
+    let mut machine = Machine::with_memory(std::mem::take(mem));
+    let mut profile = Profile::new();
+    let result = profile.run_with(&mut machine, read_input, write_output);
+    *mem = machine.mem;
+    result.map(|()| profile)
+}
@@ -0,0 +1,161 @@
+// Recording of the values a run passes in and out, and replay of it without
+// the code that produced the input.  A replay feeds the recorded inputs and
+// checks every output against the recording, stopping at the first place
+// the run differs.  Recordings are saved as text, one value per line:
+//
+//   intcode io 1
+//   out 3
+//   in 0
+//   out 4
+//
+// in the order the machine read and wrote them.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::str::FromStr;
+use super::{Machine, Memory, Event, IntcodeError};
+
+const HEADER: &str = "intcode io 1";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Io {
+    In(i64),
+    Out(i64),
+}
+
+impl fmt::Display for Io {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Io::In(val) => write!(f, "in {}", val),
+            Io::Out(val) => write!(f, "out {}", val),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    pub events: Vec<Io>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "recording line {}: {}", self.line, self.msg)
+    }
+}
+
+impl Error for RecordingError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    Fault(IntcodeError),
+    // Event `index` of the recording, or None past its end, and what the
+    // machine did instead.
+    Mismatch { index: usize, expected: Option<Io>, actual: Event },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReplayError::Fault(ref err) => write!(f, "{}", err),
+            ReplayError::Mismatch { index, expected, actual } => {
+                match expected {
+                    Some(io) => write!(f, "event {}: recorded {:?}", index, io.to_string())?,
+                    None => write!(f, "event {}: recording ended", index)?,
+                }
+                match actual {
+                    Event::NeedInput => write!(f, ", but the program asked for input"),
+                    Event::Output(val) => write!(f, ", but the program wrote {}", val),
+                    Event::Halted => write!(f, ", but the program halted"),
+                }
+            }
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+impl Recording {
+    pub fn new() -> Recording {
+        Recording::default()
+    }
+
+    pub fn save(&self, out: &mut dyn Write) -> io::Result<()> {
+        write!(out, "{}", self)
+    }
+
+    pub fn save_file(&self, path: &str) -> io::Result<()> {
+        let mut file = io::BufWriter::new(fs::File::create(path)?);
+        self.save(&mut file)?;
+        file.flush()
+    }
+
+    pub fn load_file(path: &str) -> Result<Recording, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?.parse::<Recording>()?)
+    }
+}
+
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for io in &self.events {
+            writeln!(f, "{}", io)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = RecordingError;
+
+    fn from_str(text: &str) -> Result<Recording, RecordingError> {
+        let mut lines = text.lines();
+        if lines.next().map(|line| line.trim()) != Some(HEADER) {
+            return Err(RecordingError { line: 1, msg: "not an intcode recording".to_string() });
+        }
+        let mut events = vec![];
+        for (i, line) in lines.enumerate() {
+            let err = |msg| RecordingError { line: i + 2, msg };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut parts = line.trim().splitn(2, ' ');
+            let kind = parts.next().unwrap();
+            let val = parts.next().unwrap_or("").trim();
+            let val = val.parse().map_err(|_| err(format!("invalid number {:?}", val)))?;
+            events.push(match kind {
+                "in" => Io::In(val),
+                "out" => Io::Out(val),
+                other => return Err(err(format!("expected in or out, got {:?}", other))),
+            });
+        }
+        Ok(Recording { events })
+    }
+}
+
+// Run to completion on the recorded inputs.  Outputs that match the
+// recording are passed on to `write_output`; the run must halt exactly
+// where the recording ends.
+pub fn replay<M: Memory<Word = i64>>(machine: &mut Machine<M>, recording: &Recording,
+                                     write_output: &mut dyn FnMut(i64))
+                                     -> Result<(), ReplayError> {
+    for index in 0.. {
+        let actual = machine.run().map_err(ReplayError::Fault)?;
+        let expected = recording.events.get(index).cloned();
+        match (actual, expected) {
+            (Event::NeedInput, Some(Io::In(val))) => machine.push_input(val),
+            (Event::Output(val), Some(Io::Out(recorded))) if val == recorded => write_output(val),
+            (Event::Halted, None) => break,
+            (actual, expected) => return Err(ReplayError::Mismatch { index, expected, actual }),
+        }
+    }
+    Ok(())
+}
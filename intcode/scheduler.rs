@@ -0,0 +1,68 @@
+// Cooperative scheduler running many Intcode machines in one thread.  Each
+// machine runs until it blocks on input or halts, then the next one gets a
+// turn.  Outputs go to a callback that can feed any machine's input.  The
+// run ends once no machine can make progress, and the report says which
+// machines halted and which are stuck waiting for input.
+
+use super::{Machine, Event, IntcodeError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub halted: Vec<usize>,
+    pub stuck: Vec<usize>,
+}
+
+impl Report {
+    // Some machine is waiting for input that will never come.
+    pub fn is_deadlock(&self) -> bool {
+        !self.stuck.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Scheduler {
+    pub machines: Vec<Machine>,
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler::default()
+    }
+
+    // Add a machine, returning its index.
+    pub fn add(&mut self, machine: Machine) -> usize {
+        self.machines.push(machine);
+        self.machines.len() - 1
+    }
+
+    // Round-robin until every machine has halted or is waiting on an empty
+    // input queue.  `on_output` gets all the machines, the index of the one
+    // that wrote and the value.  An error stops the run and names the
+    // machine.
+    pub fn run(&mut self, on_output: &mut dyn FnMut(&mut [Machine], usize, i64))
+               -> Result<Report, (usize, IntcodeError)> {
+        loop {
+            // a round without an executed instruction changes nothing
+            let mut progress = false;
+            for id in 0..self.machines.len() {
+                loop {
+                    match self.machines[id].step().map_err(|err| (id, err))? {
+                        None => progress = true,
+                        Some(Event::Output(val)) => {
+                            progress = true;
+                            on_output(&mut self.machines, id, val);
+                        }
+                        Some(Event::NeedInput) | Some(Event::Halted) => break,
+                    }
+                }
+            }
+            let blocked = self.machines.iter()
+                .all(|machine| machine.is_halted() || machine.input.is_empty());
+            if !progress && blocked {
+                let (halted, stuck) = (0..self.machines.len())
+                    .partition(|&id| self.machines[id].is_halted());
+                return Ok(Report { halted, stuck });
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+// Text snapshot of a complete machine state, so a run can be saved to disk
+// and resumed later.  The format is line based:
+//
+//   intcode snapshot 1
+//   pos 37
+//   relbase 2342
+//   input 1,0
+//   mem 1,380,379,385,...
+//   page 3072 0,0,5,...
+//
+// `input` holds the queued input not yet consumed by the program and may be
+// empty.  `mem` is the program image starting at address 0, and each
+// optional `page` line holds memory written past it, starting at the given
+// address.  The lines must appear in this order.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use super::{Machine, Memory};
+
+const HEADER: &str = "intcode snapshot 1";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "snapshot line {}: {}", self.line, self.msg)
+    }
+}
+
+impl Error for SnapshotError {}
+
+fn join(vals: &[i64]) -> String {
+    let words: Vec<String> = vals.iter().map(|v| v.to_string()).collect();
+    words.join(",")
+}
+
+pub fn save<M: Memory<Word = i64>>(machine: &Machine<M>, out: &mut dyn Write) -> io::Result<()> {
+    let input: Vec<i64> = machine.input.iter().cloned().collect();
+    writeln!(out, "{}", HEADER)?;
+    writeln!(out, "pos {}", machine.pos)?;
+    writeln!(out, "relbase {}", machine.relbase)?;
+    writeln!(out, "input {}", join(&input))?;
+    for (start, words) in machine.mem.segments() {
+        if start == 0 {
+            writeln!(out, "mem {}", join(words))?;
+        }
+        else {
+            writeln!(out, "page {} {}", start, join(words))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn to_string<M: Memory<Word = i64>>(machine: &Machine<M>) -> String {
+    let mut out = vec![];
+    save(machine, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+pub fn from_str(snapshot: &str) -> Result<Machine, SnapshotError> {
+    let mut lines = snapshot.lines();
+    let mut lineno = 0;
+    let mut field = |name: &str| -> Result<String, SnapshotError> {
+        lineno += 1;
+        let err = |msg| SnapshotError { line: lineno, msg };
+        let line = lines.next().ok_or_else(|| err(format!("missing {}", name)))?;
+        if name == "header" {
+            if line.trim() != HEADER {
+                return Err(err("not an intcode snapshot".to_string()));
+            }
+            return Ok(String::new());
+        }
+        let mut parts = line.splitn(2, ' ');
+        if parts.next() != Some(name) {
+            return Err(err(format!("expected {}", name)));
+        }
+        Ok(parts.next().unwrap_or("").trim().to_string())
+    };
+    fn parse_list(text: &str) -> Result<Vec<i64>, String> {
+        if text.is_empty() {
+            return Ok(vec![]);
+        }
+        text.split(",")
+            .map(|tok| tok.trim().parse().map_err(|_| format!("invalid number {:?}", tok)))
+            .collect()
+    }
+
+    field("header")?;
+    let pos = field("pos")?;
+    let relbase = field("relbase")?;
+    let input = field("input")?;
+    let mem = field("mem")?;
+    let err = |line, msg| SnapshotError { line, msg };
+    let mut machine = Machine::from_mem(parse_list(&mem).map_err(|msg| err(5, msg))?);
+    for (i, line) in lines.enumerate() {
+        let lineno = i + 6;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut parts = line.trim().splitn(3, ' ');
+        if parts.next() != Some("page") {
+            return Err(err(lineno, "expected page".to_string()));
+        }
+        let start = parts.next().unwrap_or("");
+        let start: usize = start.parse()
+            .map_err(|_| err(lineno, format!("invalid address {:?}", start)))?;
+        let words = parse_list(parts.next().unwrap_or("").trim()).map_err(|msg| err(lineno, msg))?;
+        for (i, &val) in words.iter().enumerate() {
+            machine.mem.write(start + i, val)
+                .map_err(|_| err(lineno, "page beyond the memory limit".to_string()))?;
+        }
+    }
+    machine.pos = pos.parse().map_err(|_| err(2, format!("invalid pos {:?}", pos)))?;
+    machine.relbase = relbase.parse()
+        .map_err(|_| err(3, format!("invalid relbase {:?}", relbase)))?;
+    machine.input = parse_list(&input).map_err(|msg| err(4, msg))?.into_iter().collect();
+    Ok(machine)
+}
+
+pub fn save_file<M: Memory<Word = i64>>(machine: &Machine<M>, path: &str) -> io::Result<()> {
+    let mut file = io::BufWriter::new(fs::File::create(path)?);
+    save(machine, &mut file)?;
+    file.flush()
+}
+
+pub fn load_file(path: &str) -> Result<Machine, Box<dyn Error>> {
+    Ok(from_str(&fs::read_to_string(path)?)?)
+}
@@ -0,0 +1,83 @@
+// A machine's input and output as ASCII text on a terminal: outputs in
+// 0..=127 are printed as characters, larger ones as numbers on a line of
+// their own, and each line read is fed in as character codes followed by a
+// newline.  Running out of lines stops the run with InputExhausted.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::prelude::*;
+use super::{Machine, Memory, IntcodeError};
+use super::device::{self, Ascii, Text};
+
+#[derive(Debug)]
+pub enum TerminalError {
+    Fault(IntcodeError),
+    Io(io::Error),
+}
+
+impl fmt::Display for TerminalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TerminalError::Fault(ref err) => write!(f, "{}", err),
+            TerminalError::Io(ref err) => write!(f, "terminal: {}", err),
+        }
+    }
+}
+
+impl Error for TerminalError {}
+
+pub fn run<M: Memory<Word = i64>>(machine: &mut Machine<M>, input: &mut dyn BufRead,
+                                  output: &mut dyn Write) -> Result<(), TerminalError> {
+    // I/O errors, reported once the run stops
+    let mut io_error = None;
+    let mut out_error = None;
+    let result = {
+        let mut at_line_start = true;
+        let read_line = || {
+            let mut line = String::new();
+            match input.read_line(&mut line) {
+                Ok(0) => None,
+                Ok(_) => {
+                    while line.ends_with('\n') || line.ends_with('\r') {
+                        line.pop();
+                    }
+                    Some(line)
+                }
+                Err(err) => {
+                    io_error = Some(err);
+                    None
+                }
+            }
+        };
+        let on_text = |text| {
+            if out_error.is_some() {
+                return;
+            }
+            let written = match text {
+                Text::Line(line) => {
+                    at_line_start = true;
+                    writeln!(output, "{}", line)
+                }
+                Text::Partial(line) => {
+                    at_line_start = false;
+                    write!(output, "{}", line).and_then(|_| output.flush())
+                }
+                Text::Value(val) => {
+                    let newline = if at_line_start {""} else {"\n"};
+                    at_line_start = true;
+                    writeln!(output, "{}{}", newline, val)
+                }
+            };
+            if let Err(err) = written {
+                out_error = Some(err);
+            }
+        };
+        device::run(machine, &mut Ascii::new(read_line, on_text))
+    };
+    if let Some(err) = io_error.or(out_error) {
+        return Err(TerminalError::Io(err));
+    }
+    result.map_err(TerminalError::Fault)?;
+    output.flush().map_err(TerminalError::Io)
+}
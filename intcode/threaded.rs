@@ -0,0 +1,106 @@
+// Intcode machine running on its own thread.  Inputs are sent to it and
+// its outputs come back as messages, with the end of the run reported as a
+// message of its own, so a halt is never confused with an output value.
+//
+//   let vm = threaded::spawn(Machine::new(&prog));
+//   vm.send(1);
+//   while let Some(Message::Output(val)) = vm.recv() { ... }
+//   vm.cancel();
+//
+// Cancelling stops the machine before its next instruction, or while it is
+// waiting for input.  Dropping the handle cancels it too.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use super::{Machine, Event, IntcodeError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Output(i64),
+    // the last message, telling how the run ended
+    Halted,
+    Cancelled,
+    Error(IntcodeError),
+}
+
+pub struct Handle {
+    // None wakes up a machine waiting for input after a cancel
+    input: Sender<Option<i64>>,
+    output: Receiver<Message>,
+    cancelled: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<Machine>>,
+}
+
+pub fn spawn(machine: Machine) -> Handle {
+    let (input, thread_input) = channel();
+    let (thread_output, output) = channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancelled);
+    let thread = thread::spawn(move || run(machine, thread_input, thread_output, flag));
+    Handle { input, output, cancelled, thread: Some(thread) }
+}
+
+fn run(mut machine: Machine, input: Receiver<Option<i64>>, output: Sender<Message>,
+       cancelled: Arc<AtomicBool>) -> Machine {
+    let last = loop {
+        if cancelled.load(Ordering::SeqCst) {
+            break Message::Cancelled;
+        }
+        match machine.step() {
+            Ok(None) => (),
+            Ok(Some(Event::Output(val))) => {
+                if output.send(Message::Output(val)).is_err() {
+                    // nobody is listening any more
+                    return machine;
+                }
+            }
+            Ok(Some(Event::NeedInput)) => match input.recv() {
+                Ok(Some(val)) => machine.push_input(val),
+                Ok(None) | Err(_) => break Message::Cancelled,
+            },
+            Ok(Some(Event::Halted)) => break Message::Halted,
+            Err(err) => break Message::Error(err),
+        }
+    };
+    let _ = output.send(last);
+    machine
+}
+
+impl Handle {
+    // Inputs sent after the machine stopped are ignored.
+    pub fn send(&self, val: i64) {
+        let _ = self.input.send(Some(val));
+    }
+
+    // The next message, or None once the last one has been received.
+    pub fn recv(&self) -> Option<Message> {
+        self.output.recv().ok()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        let _ = self.input.send(None);
+    }
+
+    // Wait for the machine to stop and return it as it was left.  Messages
+    // not received yet are dropped.  A machine waiting for input, or looping
+    // forever, never stops on its own, so this blocks until it is sent input
+    // or cancel() is called first.
+    pub fn join(mut self) -> Machine {
+        let thread = self.thread.take().unwrap();
+        match thread.join() {
+            Ok(machine) => machine,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            self.cancel();
+        }
+    }
+}
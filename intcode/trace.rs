@@ -0,0 +1,207 @@
+// Execution trace of an Intcode machine, one record per executed
+// instruction.  Records are written one per line, in one of two formats.
+//
+// Text:
+//
+//   <pc> <mnemonic> rb=<relbase> <operand>... [-> <operand>]
+//
+// where each operand read is `#<value>` for immediate mode, `[<addr>]=<value>`
+// for position mode and `rb<+-offset>[<addr>]=<value>` for relative mode.
+// The operand after `->` is the destination of a write, shown with the value
+// written.  `relbase` is the relative base before the instruction executes.
+// For example:
+//
+//   2 add rb=0 [9]=10 #5 -> [9]=15
+//   19 out rb=2000 rb-3[1997]=7
+//
+// JSON, one object per line with the same information:
+//
+//   {"pc":2,"op":"add","rb":0,"operands":[{"mode":"position","raw":9,
+//    "addr":9,"value":10},{"mode":"immediate","raw":5,"value":5}],
+//    "write":{"mode":"position","raw":9,"addr":9,"value":15}}
+//
+// `write` is null for instructions that do not write memory.  Halting is not
+// recorded, nor are input instructions that wait for input.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::prelude::*;
+use super::{AddrMode, Opcode, Machine, Memory, Event, IntcodeError,
+            decode_param_modes};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operand {
+    pub mode: AddrMode,
+    pub raw: i64,
+    // resolved address, None in immediate mode or when the relative
+    // address overflows
+    pub addr: Option<i64>,
+    // value read, or for the write operand, the value written
+    pub value: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRecord {
+    pub pc: usize,
+    pub opcode: Opcode,
+    pub relbase: i64,
+    pub operands: Vec<Operand>,
+    pub write: Option<Operand>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TraceFormat {
+    Text,
+    Json,
+}
+
+fn resolve<M: Memory<Word = i64>>(machine: &Machine<M>, mode: AddrMode, raw: i64) -> Operand {
+    let addr = match mode {
+        AddrMode::POSITION => Some(raw),
+        AddrMode::IMMEDIATE => None,
+        AddrMode::RELATIVE => machine.relbase.checked_add(raw),
+    };
+    let value = match addr {
+        Some(addr) if addr >= 0 => machine.mem.read(addr as usize),
+        Some(_) => 0,
+        None => raw,
+    };
+    Operand { mode, raw, addr, value }
+}
+
+impl TraceRecord {
+    // Record the instruction the machine is about to execute, or None if it
+    // is not a valid instruction.  The value of the write operand is filled
+    // in by `finish()`.
+    pub fn begin<M: Memory<Word = i64>>(machine: &Machine<M>) -> Option<TraceRecord> {
+        let pc = machine.pos;
+        let (opcode, modes) = decode_param_modes(machine.mem.read(pc)).ok()?;
+        let mut operands: Vec<Operand> = modes.iter().enumerate()
+            .map(|(i, &mode)| resolve(machine, mode, machine.mem.read(pc + 1 + i)))
+            .collect();
+        let write = opcode.write_param().map(|i| operands.remove(i));
+        Some(TraceRecord { pc, opcode, relbase: machine.relbase, operands, write })
+    }
+
+    pub fn finish<M: Memory<Word = i64>>(&mut self, machine: &Machine<M>) {
+        if let Some(ref mut write) = self.write {
+            if let Some(addr) = write.addr {
+                write.value = machine.mem.read(addr as usize);
+            }
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        fn fmt_operand(op: &Operand) -> String {
+            let addr = op.addr.map_or("?".to_string(), |addr| addr.to_string());
+            match op.mode {
+                AddrMode::IMMEDIATE => format!("#{}", op.value),
+                AddrMode::POSITION => format!("[{}]={}", addr, op.value),
+                AddrMode::RELATIVE => format!("rb{:+}[{}]={}", op.raw, addr, op.value),
+            }
+        }
+        let mut line = format!("{} {} rb={}", self.pc, self.opcode.mnemonic(), self.relbase);
+        for op in &self.operands {
+            line.push(' ');
+            line.push_str(&fmt_operand(op));
+        }
+        if let Some(ref write) = self.write {
+            line.push_str(" -> ");
+            line.push_str(&fmt_operand(write));
+        }
+        line
+    }
+
+    pub fn to_json(&self) -> String {
+        fn fmt_operand(op: &Operand) -> String {
+            let mode = match op.mode {
+                AddrMode::POSITION => "position",
+                AddrMode::IMMEDIATE => "immediate",
+                AddrMode::RELATIVE => "relative",
+            };
+            match op.addr {
+                Some(addr) => format!(r#"{{"mode":"{}","raw":{},"addr":{},"value":{}}}"#,
+                                      mode, op.raw, addr, op.value),
+                None => format!(r#"{{"mode":"{}","raw":{},"value":{}}}"#,
+                                mode, op.raw, op.value),
+            }
+        }
+        let operands: Vec<String> = self.operands.iter().map(fmt_operand).collect();
+        format!(r#"{{"pc":{},"op":"{}","rb":{},"operands":[{}],"write":{}}}"#,
+                self.pc, self.opcode.mnemonic(), self.relbase, operands.join(","),
+                self.write.as_ref().map(fmt_operand).unwrap_or_else(|| "null".to_string()))
+    }
+}
+
+#[derive(Debug)]
+pub enum TraceError {
+    Fault(IntcodeError),
+    Io(io::Error),
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TraceError::Fault(ref err) => write!(f, "{}", err),
+            TraceError::Io(ref err) => write!(f, "trace: {}", err),
+        }
+    }
+}
+
+impl Error for TraceError {}
+
+pub struct Tracer<'a> {
+    pub out: &'a mut dyn Write,
+    pub format: TraceFormat,
+}
+
+impl<'a> Tracer<'a> {
+    pub fn new(out: &'a mut dyn Write, format: TraceFormat) -> Tracer<'a> {
+        Tracer { out, format }
+    }
+
+    fn emit(&mut self, record: &TraceRecord) -> io::Result<()> {
+        match self.format {
+            TraceFormat::Text => writeln!(self.out, "{}", record.to_text()),
+            TraceFormat::Json => writeln!(self.out, "{}", record.to_json()),
+        }
+    }
+
+    // Like Machine::step(), but also writes a trace record for the executed
+    // instruction.  A failed trace write stops the run after the instruction
+    // it records has executed.
+    pub fn step<M: Memory<Word = i64>>(&mut self, machine: &mut Machine<M>)
+                           -> Result<Option<Event>, TraceError> {
+        let mut record = TraceRecord::begin(machine);
+        let event = machine.step().map_err(TraceError::Fault)?;
+        match event {
+            Some(Event::NeedInput) | Some(Event::Halted) => (),
+            _ => if let Some(ref mut record) = record {
+                record.finish(machine);
+                self.emit(record).map_err(TraceError::Io)?;
+            }
+        }
+        Ok(event)
+    }
+
+    pub fn run<M: Memory<Word = i64>>(&mut self, machine: &mut Machine<M>) -> Result<Event, TraceError> {
+        loop {
+            if let Some(event) = self.step(machine)? {
+                return Ok(event);
+            }
+        }
+    }
+
+    pub fn run_with<M: Memory<Word = i64>>(&mut self, machine: &mut Machine<M>,
+                               read_input: &mut dyn FnMut() -> i64,
+                               write_output: &mut dyn FnMut(i64)) -> Result<(), TraceError> {
+        loop {
+            match self.run(machine)? {
+                Event::NeedInput => machine.push_input(read_input()),
+                Event::Output(val) => write_output(val),
+                Event::Halted => return Ok(()),
+            }
+        }
+    }
+}
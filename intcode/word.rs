@@ -0,0 +1,101 @@
+// Word types the virtual machine can run on.  Arithmetic is checked, so a
+// program whose values outgrow the type fails with an Overflow error; i128
+// or bigint::BigInt give such programs room to run.
+
+use std::error::Error;
+use std::fmt;
+
+// Small constants like 0, 1 and 99 come from i32, which every word holds.
+pub trait Word: Clone + PartialEq + PartialOrd + fmt::Debug + fmt::Display + From<i32> {
+    // None if the value does not fit the word.
+    fn from_i64(val: i64) -> Option<Self>;
+    fn to_i64(&self) -> Option<i64>;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+}
+
+impl Word for i32 {
+    fn from_i64(val: i64) -> Option<i32> {
+        if val < i32::MIN as i64 || val > i32::MAX as i64 {
+            None
+        }
+        else {
+            Some(val as i32)
+        }
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        Some(*self as i64)
+    }
+
+    fn checked_add(&self, other: &i32) -> Option<i32> {
+        i32::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &i32) -> Option<i32> {
+        i32::checked_mul(*self, *other)
+    }
+}
+
+impl Word for i64 {
+    fn from_i64(val: i64) -> Option<i64> {
+        Some(val)
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        Some(*self)
+    }
+
+    fn checked_add(&self, other: &i64) -> Option<i64> {
+        i64::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &i64) -> Option<i64> {
+        i64::checked_mul(*self, *other)
+    }
+}
+
+impl Word for i128 {
+    fn from_i64(val: i64) -> Option<i128> {
+        Some(val as i128)
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        if *self < i64::MIN as i128 || *self > i64::MAX as i128 {
+            None
+        }
+        else {
+            Some(*self as i64)
+        }
+    }
+
+    fn checked_add(&self, other: &i128) -> Option<i128> {
+        i128::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &i128) -> Option<i128> {
+        i128::checked_mul(*self, *other)
+    }
+}
+
+// A program word that does not fit the word type it is loaded as.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub index: usize,
+    pub val: i64,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "overflow loading word {}: {} does not fit", self.index, self.val)
+    }
+}
+
+impl Error for Overflow {}
+
+// A loaded program as words of type W.
+pub fn convert<W: Word>(prog: &[i64]) -> Result<Vec<W>, Overflow> {
+    prog.iter().enumerate()
+        .map(|(index, &val)| W::from_i64(val).ok_or(Overflow { index, val }))
+        .collect()
+}
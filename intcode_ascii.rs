@@ -0,0 +1,49 @@
+mod intcode;
+
+use std::io;
+use std::error::Error;
+use intcode::{Machine, ErrorKind};
+use intcode::terminal::{self, TerminalError};
+use intcode::load;
+
+const USAGE: &str = "usage: intcode_ascii PROGRAM";
+
+fn session(prog: &[i64], typed: &str) -> (String, Result<(), TerminalError>) {
+    let mut out = vec![];
+    let result = terminal::run(&mut Machine::new(prog), &mut typed.as_bytes(), &mut out);
+    (String::from_utf8(out).unwrap(), result)
+}
+
+fn run_tests() {
+    // prints "> ", echoes a line, prints 1000 after a partial "ok"
+    let prog = [104, 62, 104, 32,
+                3, 100, 4, 100, 1008, 100, 10, 101, 1006, 101, 4,
+                104, 111, 104, 107, 104, 1000, 104, 33, 104, 10, 99];
+    let (out, result) = session(&prog, "hi\r\n");
+    assert!(result.is_ok());
+    assert_eq!(out, "> hi\nok\n1000\n!\n");
+
+    // end of input while the program wants more
+    let (out, result) = session(&prog, "");
+    assert_eq!(out, "> ");
+    match result {
+        Err(TerminalError::Fault(ref err)) if err.kind == ErrorKind::InputExhausted => (),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    let prog = load::from_file(&path)?;
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    terminal::run(&mut Machine::new(&prog), &mut stdin.lock(), &mut stdout.lock())?;
+    Ok(())
+}
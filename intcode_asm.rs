@@ -0,0 +1,71 @@
+mod intcode;
+
+use std::io;
+use std::io::prelude::*;
+use std::error::Error;
+use std::fs;
+use intcode::asm::assemble;
+use intcode::disasm::disassemble;
+use intcode::load;
+
+fn run_tests() {
+    // the quine from 9b
+    assert_eq!(assemble("
+            arb #1
+    loop:   out rb-1
+            add [100], #1, [100]
+            eq [100], #16, [101]
+            jz [101], #loop-2       ; restart at the arb
+            hlt
+    ").unwrap(),
+               vec![109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99]);
+    assert_eq!(assemble("
+            mul #34915192, #34915192, [result]
+            out [result]
+            hlt
+    result: data 0
+    ").unwrap(),
+               vec![1102,34915192,34915192,7,4,7,99,0]);
+    assert_eq!(assemble("out #1125899906842624\nhlt").unwrap(),
+               vec![104,1125899906842624,99]);
+
+    for &(source, line) in &[("hlt\nadd #1, #2, #3", 2),
+                             ("\n\nfoo [1]", 3),
+                             ("jnz #1, #nowhere", 1),
+                             ("x: hlt\nx: hlt", 2),
+                             ("out [1], [2]", 1),
+                             ("hlt\n0: hlt", 2)] {
+        assert_eq!(assemble(source).unwrap_err().line, line);
+    }
+
+    for &input in &[include_str!("9.input"),
+                    include_str!("13.input"),
+                    include_str!("15.input")] {
+        let prog = load::parse(input).unwrap();
+        assert_eq!(assemble(&disassemble(&prog)).unwrap(), prog);
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+
+    let source = match std::env::args().nth(1) {
+        Some(path) => fs::read_to_string(path)?,
+        None => {
+            let mut source = String::new();
+            io::stdin().read_to_string(&mut source)?;
+            source
+        }
+    };
+    match assemble(&source) {
+        Ok(prog) => {
+            let words: Vec<String> = prog.iter().map(|v| v.to_string()).collect();
+            println!("{}", words.join(","));
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
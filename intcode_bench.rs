@@ -0,0 +1,226 @@
+mod intcode;
+mod compiled;
+
+use std::error::Error;
+use std::time::{Duration, Instant};
+use intcode::{Machine, Event, ErrorKind, IntcodeError, Memory, interpret};
+use intcode::predecode::{Predecoded, interpret_predecoded};
+use intcode::load;
+
+const USAGE: &str = "usage: intcode_bench [ROUNDS]";
+
+trait Engine: Clone {
+    fn load(prog: &[i64]) -> Self;
+    fn set(&mut self, addr: usize, val: i64);
+    fn push_input(&mut self, val: i64);
+    fn run(&mut self) -> Result<Event, IntcodeError>;
+    fn read(&self, addr: usize) -> i64;
+}
+
+impl Engine for Machine {
+    fn load(prog: &[i64]) -> Machine {
+        Machine::new(prog)
+    }
+
+    fn set(&mut self, addr: usize, val: i64) {
+        self.mem.write(addr, val).unwrap()
+    }
+
+    fn push_input(&mut self, val: i64) {
+        Machine::push_input(self, val)
+    }
+
+    fn run(&mut self) -> Result<Event, IntcodeError> {
+        Machine::run(self)
+    }
+
+    fn read(&self, addr: usize) -> i64 {
+        self.mem.read(addr)
+    }
+}
+
+impl Engine for Predecoded {
+    fn load(prog: &[i64]) -> Predecoded {
+        Predecoded::new(prog)
+    }
+
+    fn set(&mut self, addr: usize, val: i64) {
+        self.machine.mem.write(addr, val).unwrap()
+    }
+
+    fn push_input(&mut self, val: i64) {
+        Predecoded::push_input(self, val)
+    }
+
+    fn run(&mut self) -> Result<Event, IntcodeError> {
+        Predecoded::run(self)
+    }
+
+    fn read(&self, addr: usize) -> i64 {
+        self.machine.mem.read(addr)
+    }
+}
+
+// Day 2b: run every noun/verb pair, returning the checksum of the pair that
+// produces 19690720.  Each run starts from a copy of the loaded program.
+fn noun_verb<E: Engine>(prog: &[i64]) -> Option<i64> {
+    let loaded = E::load(prog);
+    let mut found = None;
+    for noun in 0..100 {
+        for verb in 0..100 {
+            let mut engine = loaded.clone();
+            engine.set(1, noun);
+            engine.set(2, verb);
+            assert_eq!(engine.run(), Ok(Event::Halted));
+            if engine.read(0) == 19690720 {
+                found = Some(100 * noun + verb);
+            }
+        }
+    }
+    found
+}
+
+fn amplify<E: Engine>(loaded: &E, phases: &[i64]) -> i64 {
+    let mut amps: Vec<E> = phases.iter()
+        .map(|&phase| {
+            let mut amp = loaded.clone();
+            amp.push_input(phase);
+            amp
+        })
+        .collect();
+    let mut thrust = 0;
+    loop {
+        for amp in amps.iter_mut() {
+            amp.push_input(thrust);
+            match amp.run().unwrap() {
+                Event::Output(val) => thrust = val,
+                Event::Halted => return thrust,
+                Event::NeedInput => panic!("amplifier requested extra input"),
+            }
+        }
+    }
+}
+
+fn permutations(items: &[i64]) -> Vec<Vec<i64>> {
+    if items.is_empty() {
+        return vec![vec![]];
+    }
+    let mut ret = vec![];
+    for i in 0..items.len() {
+        let mut rest = items.to_vec();
+        let first = rest.remove(i);
+        for mut perm in permutations(&rest) {
+            perm.insert(0, first);
+            ret.push(perm);
+        }
+    }
+    ret
+}
+
+// Day 7b: the best thrust over all 120 phase settings of the feedback loop.
+fn max_thrust<E: Engine>(prog: &[i64]) -> i64 {
+    let loaded = E::load(prog);
+    permutations(&[5, 6, 7, 8, 9]).iter()
+        .map(|phases| amplify(&loaded, phases))
+        .max()
+        .unwrap()
+}
+
+fn outputs(prog: &[i64], input: i64, predecoded: bool) -> Result<Vec<i64>, IntcodeError> {
+    let mut mem = prog.to_vec();
+    let mut out = vec![];
+    if predecoded {
+        interpret_predecoded(&mut mem, &mut || input, &mut |val| out.push(val))?;
+    }
+    else {
+        interpret(&mut mem, &mut || input, &mut |val| out.push(val))?;
+    }
+    Ok(out)
+}
+
+fn run_tests() {
+    // the write to address 1 changes the operand of the output instruction
+    // after it has been decoded once
+    let prog = [104,7,1001,1,1,1,1008,1,9,14,1006,14,0,99,0];
+    assert_eq!(outputs(&prog, 0, true), Ok(vec![7, 8]));
+    // the write to address 0 turns the output instruction into an input
+    let prog = [4,12,1101,0,3,0,1006,12,0,4,12,99,0];
+    assert_eq!(outputs(&prog, 5, false), Ok(vec![0, 5]));
+    assert_eq!(outputs(&prog, 5, true), Ok(vec![0, 5]));
+
+    for prog in &[vec![1101,1,1,0,42], vec![301,0,0,0,99], vec![11101,1,1,0,99],
+                  vec![1,-1,0,0,99], vec![1102,4611686018427387904,2,0,99],
+                  vec![3,-1,99], vec![1105,1,-5], vec![109,i64::MAX,109,1,99],
+                  vec![109,i64::MAX,204,1,99]] {
+        assert_eq!(outputs(prog, 0, false), outputs(prog, 0, true));
+    }
+    assert_eq!(outputs(&[1105,1,-5], 0, true).unwrap_err().kind, ErrorKind::NegativeAddress(-5));
+
+    let prog = load::from_bytes(include_bytes!("9.input")).unwrap();
+    for &input in &[1, 2] {
+        assert_eq!(outputs(&prog, input, false), outputs(&prog, input, true));
+    }
+    let mut reference = vec![];
+    let mut predecoded = vec![];
+    let mut mem = load::from_bytes(include_bytes!("13.input")).unwrap();
+    mem[0] = 2;
+    let mut fast_mem = mem.clone();
+    interpret(&mut mem, &mut || 0, &mut |val| reference.push(val)).unwrap();
+    interpret_predecoded(&mut fast_mem, &mut || 0, &mut |val| predecoded.push(val)).unwrap();
+    assert_eq!(reference, predecoded);
+    assert_eq!(mem, fast_mem);
+}
+
+fn time<T>(rounds: u32, f: impl Fn() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let mut ret = f();
+    for _ in 1..rounds {
+        ret = f();
+    }
+    (ret, start.elapsed() / rounds)
+}
+
+fn compare<T: PartialEq + std::fmt::Debug>(name: &str, rounds: u32,
+                                           reference: impl Fn() -> T,
+                                           engine: &str, other: impl Fn() -> T) {
+    let (expected, slow) = time(rounds, reference);
+    let (got, fast) = time(rounds, other);
+    assert_eq!(expected, got, "{}: engines disagree", name);
+    println!("{}: reference {:.2?}, {} {:.2?} ({:.2}x), result {:?}",
+             name, slow, engine, fast, slow.as_secs_f64() / fast.as_secs_f64(), got);
+}
+
+// noun_verb() with the module intcode_compile generated from 2.input.
+fn noun_verb_compiled(prog: &[i64]) -> Option<i64> {
+    let mut found = None;
+    for noun in 0..100 {
+        for verb in 0..100 {
+            let mut mem = prog.to_vec();
+            mem[1] = noun;
+            mem[2] = verb;
+            compiled::day2::interpret(&mut mem, &mut || panic!("no input"), &mut |_| ()).unwrap();
+            if mem[0] == 19690720 {
+                found = Some(100 * noun + verb);
+            }
+        }
+    }
+    found
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+
+    let rounds: u32 = match std::env::args().nth(1) {
+        Some(arg) => arg.parse().ok().filter(|&rounds| rounds > 0).ok_or(USAGE)?,
+        None => 3,
+    };
+    let prog = load::from_bytes(include_bytes!("2.input")).unwrap();
+    compare("2b noun/verb search", rounds, || noun_verb::<Machine>(&prog),
+            "predecoded", || noun_verb::<Predecoded>(&prog));
+    compare("2b noun/verb search", rounds, || noun_verb::<Machine>(&prog),
+            "compiled", || noun_verb_compiled(&prog));
+    let prog = load::from_bytes(include_bytes!("7.input")).unwrap();
+    compare("7b feedback permutations", rounds, || max_thrust::<Machine>(&prog),
+            "predecoded", || max_thrust::<Predecoded>(&prog));
+    Ok(())
+}
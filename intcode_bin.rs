@@ -0,0 +1,111 @@
+mod intcode;
+
+use std::error::Error;
+use std::fs;
+use intcode::{Machine, Memory, Event};
+use intcode::binary::{self, Contents};
+use intcode::{load, snapshot};
+
+const USAGE: &str = "usage: intcode_bin (encode | decode) INPUT OUTPUT";
+
+const SNAPSHOT_HEADER: &str = "intcode snapshot";
+
+// A program or text snapshot in binary form.
+fn encode(text: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if text.starts_with(SNAPSHOT_HEADER) {
+        Ok(binary::snapshot_to_bytes(&snapshot::from_str(text)?))
+    }
+    else {
+        Ok(binary::image_to_bytes(&load::parse(text)?))
+    }
+}
+
+// Back to a program in the .input format, or to a text snapshot.
+fn decode(bytes: &[u8]) -> Result<String, Box<dyn Error>> {
+    Ok(match binary::from_bytes(bytes)? {
+        Contents::Image(words) => {
+            let words: Vec<String> = words.iter().map(|val| val.to_string()).collect();
+            words.join(",") + "\n"
+        }
+        Contents::Snapshot(machine) => snapshot::to_string(&machine),
+    })
+}
+
+fn run_tests() {
+    let inputs: &[(&str, &str)] = &[("2.input", include_str!("2.input")),
+                                    ("5.input", include_str!("5.input")),
+                                    ("7.input", include_str!("7.input")),
+                                    ("9.input", include_str!("9.input")),
+                                    ("11.input", include_str!("11.input")),
+                                    ("13.input", include_str!("13.input")),
+                                    ("15.input", include_str!("15.input"))];
+    for &(name, text) in inputs {
+        let bytes = encode(text).unwrap();
+        assert_eq!(decode(&bytes).unwrap(), text, "{} does not round-trip", name);
+        assert_eq!(load::from_bytes(&bytes).unwrap(), load::parse(text).unwrap());
+        assert!(bytes.len() < text.len(), "{} grew", name);
+    }
+
+    // small values take a byte, negative ones included
+    assert_eq!(binary::image_to_bytes(&[0, -1, 1, 63, -64, 64]),
+               b"ICB\x01\x08\x00\x06\x00\x01\x02\x7e\x7f\x80\x01".to_vec());
+    let extremes = [i64::MIN, i64::MAX, -1 << 40, 1 << 62];
+    match binary::from_bytes(&binary::image_to_bytes(&extremes)) {
+        Ok(Contents::Image(ref words)) if words == &extremes => (),
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    // a machine stopped halfway, with memory past the image and input left
+    let mut machine = Machine::new(&[109, -7, 21101, 5, 0, 1040, 3, 50, 99]);
+    assert_eq!(machine.run(), Ok(Event::NeedInput));
+    machine.push_input(-2);
+    machine.push_input(8);
+    let bytes = binary::snapshot_to_bytes(&machine);
+    assert_eq!(binary::from_bytes(&bytes), Ok(Contents::Snapshot(machine.clone())));
+    assert_eq!(encode(&decode(&bytes).unwrap()).unwrap(), bytes);
+    let mut resumed = match binary::from_bytes(&bytes).unwrap() {
+        Contents::Snapshot(machine) => machine,
+        other => panic!("unexpected contents {:?}", other),
+    };
+    resumed.run().unwrap();
+    assert_eq!((resumed.mem.read(1033), resumed.mem.read(50)), (5, -2));
+    assert_eq!(resumed.input, vec![8]);
+    assert!(load::from_bytes(&bytes).is_err());
+
+    let good = binary::image_to_bytes(&[1, 300, 99]);
+    for &(ref bytes, offset) in &[(b"IC".to_vec(), 0),
+                                  (b"ICB\x02\x08\x00\x00".to_vec(), 3),
+                                  (b"ICB\x01\x10\x00\x00".to_vec(), 4),
+                                  (b"ICB\x01\x00\x00\x00".to_vec(), 4),
+                                  (b"ICB\x01\x08\x04\x00".to_vec(), 5),
+                                  (good[..good.len() - 2].to_vec(), 10),
+                                  ([&good[..], b"\x00"].concat(), 12),
+                                  (b"ICB\x01\x08\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02".to_vec(), 7),
+                                  // 300 does not fit a byte
+                                  (b"ICB\x01\x01\x00\x02\x02\xd8\x04".to_vec(), 8)] {
+        assert_eq!(binary::from_bytes(bytes).unwrap_err().offset, offset, "{:?}", bytes);
+    }
+    assert_eq!(binary::from_bytes(b"ICB\x01\x10\x00\x00").unwrap_err().to_string(),
+               "binary image byte 4: unsupported word size 16, only up to 8 bytes load");
+    // narrower words load as long as they fit
+    assert_eq!(binary::from_bytes(b"ICB\x01\x02\x00\x02\xd8\x04\xff\x03"),
+               Ok(Contents::Image(vec![300, -256])));
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() != 3 {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    }
+    match args[0].as_str() {
+        "encode" => fs::write(&args[2], encode(&fs::read_to_string(&args[1])?)?)?,
+        "decode" => fs::write(&args[2], decode(&fs::read(&args[1])?)?)?,
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+mod intcode;
+
+use std::error::Error;
+use intcode::cfg::{analyze, CodeWrite};
+use intcode::load;
+
+const USAGE: &str = "usage: intcode_cfg [--report] PROGRAM";
+
+fn run_tests() {
+    //  0: in [18]
+    //  2: add [18], #-1, [18]
+    //  6: jnz [18], #2
+    //  9: add #7, #0, [14]      rewrites the operand of the out
+    // 13: out #0
+    // 15: jz #0, rb+0
+    let prog = [3,18,1001,18,-1,18,1005,18,2,1101,7,0,14,104,0,2106,0,0,0];
+    let cfg = analyze(&prog);
+    let starts: Vec<usize> = cfg.blocks.keys().cloned().collect();
+    assert_eq!(starts, vec![0, 2, 9]);
+    assert_eq!(cfg.blocks[&0].succs, vec![2]);
+    assert_eq!(cfg.blocks[&2].succs, vec![2, 9]);
+    assert!(cfg.blocks[&9].indirect);
+    assert_eq!(cfg.indirect_jumps(), vec![15]);
+    assert_eq!(cfg.code_writes, vec![CodeWrite { writer: 9, addr: 14, instr: 13 }]);
+    assert_eq!(cfg.to_dot(), concat!(
+        "digraph intcode {\n",
+        "    node [shape=box, fontname=\"monospace\"];\n",
+        "    b0 [label=\"0: in [18]\\l\"];\n",
+        "    b2 [label=\"2: add [18], #-1, [18]\\l6: jnz [18], #2\\l\"];\n",
+        "    b9 [label=\"9: add #7, #0, [14]\\l13: out #0 *\\l15: jz #0, rb+0\\l\", color=red];\n",
+        "    indirect [shape=ellipse];\n",
+        "    b0 -> b2;\n",
+        "    b2 -> b2;\n",
+        "    b2 -> b9;\n",
+        "    b9 -> indirect [style=dashed];\n",
+        "}\n"));
+
+    // the game returns from subroutines through the stack, and both
+    // programs patch operands of their own instructions
+    let cfg = analyze(&load::from_bytes(include_bytes!("13.input")).unwrap());
+    assert_eq!(cfg.indirect_jumps(), vec![453, 546, 575, 598, 636]);
+    assert!(cfg.code_writes.contains(&CodeWrite { writer: 563, addr: 0, instr: 0 }));
+    let cfg = analyze(&load::from_bytes(include_bytes!("15.input")).unwrap());
+    assert!(cfg.indirect_jumps().is_empty());
+    assert_eq!(cfg.code_writes, vec![CodeWrite { writer: 206, addr: 211, instr: 210 }]);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let report = args.first().map(|arg| arg == "--report") == Some(true);
+    if report {
+        args.remove(0);
+    }
+    if args.len() != 1 {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    }
+    let cfg = analyze(&load::from_file(&args[0])?);
+    if report {
+        print!("{}", cfg.report());
+    }
+    else {
+        print!("{}", cfg.to_dot());
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+mod intcode;
+mod compiled;
+
+use std::error::Error;
+use intcode::{interpret, ArithOp, ErrorKind, IntcodeError};
+use intcode::compile::compile;
+use intcode::load;
+
+const USAGE: &str = "usage: intcode_compile [--var ADDR]... PROGRAM";
+
+type Interpret = fn(&mut Vec<i64>, &mut dyn FnMut() -> i64, &mut dyn FnMut(i64))
+                    -> Result<(), IntcodeError>;
+
+// Run a compiled module and the interpreter on `prog`, checking they agree,
+// and return the outputs.
+fn run(compiled: Interpret, prog: &[i64], input: i64) -> Result<Vec<i64>, IntcodeError> {
+    let mut mem = prog.to_vec();
+    let mut out = vec![];
+    let result = compiled(&mut mem, &mut || input, &mut |val| out.push(val));
+    let mut expected_mem = prog.to_vec();
+    let mut expected_out = vec![];
+    let expected = interpret(&mut expected_mem, &mut || input, &mut |val| expected_out.push(val));
+    assert_eq!((&result, &out, &mem), (&expected, &expected_out, &expected_mem));
+    result.map(|()| out)
+}
+
+fn run_tests() {
+    // each module under compiled/ is up to date with the program and
+    // variable words it was generated from
+    for &(name, prog, variable, source) in compiled::SOURCES {
+        let prog = load::parse(prog).unwrap();
+        assert!(compile(&prog, variable) == source, "compiled/{}.rs is out of date", name);
+    }
+
+    // the same tests as 9b's
+    let quine = [109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99];
+    assert_eq!(run(compiled::quine::interpret, &quine, 0), Ok(quine.to_vec()));
+    assert!(run(compiled::large_mul::interpret, &[1102,34915192,34915192,7,4,7,99,0], 0)
+            .unwrap()[0] >= 1_000_000_000_000_000);
+    assert_eq!(run(compiled::large_out::interpret, &[104,1125899906842624,99], 0),
+               Ok(vec![1125899906842624]));
+
+    let err = run(compiled::bad_opcode::interpret, &[1101,1,1,0,42], 0).unwrap_err();
+    assert_eq!((err.pc, err.instruction, err.kind), (4, 42, ErrorKind::InvalidOpcode(42)));
+    let err = run(compiled::bad_mode::interpret, &[301,0,0,0,99], 0).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidMode(3));
+    let err = run(compiled::immediate_write::interpret, &[11101,1,1,0,99], 0).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ImmediateWrite);
+    let err = run(compiled::negative_address::interpret, &[1,-1,0,0,99], 0).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NegativeAddress(-1));
+    let err = run(compiled::overflow::interpret, &[1102,4611686018427387904,2,0,99], 0).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Overflow { op: ArithOp::Mul, lhs: 4611686018427387904, rhs: 2 });
+    let err = run(compiled::far_write::interpret, &[1101,1,1,1 << 40,99], 0).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::AddressTooLarge(1 << 40));
+    let err = run(compiled::relative_address::interpret, &[109,i64::MAX,204,1,99], 0).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Overflow { op: ArithOp::Add, lhs: i64::MAX, rhs: 1 });
+    let err = run(compiled::relative_base::interpret, &[109,i64::MAX,109,1,99], 0).unwrap_err();
+    assert_eq!((err.pc, err.kind), (2, ErrorKind::Overflow { op: ArithOp::Add, lhs: i64::MAX, rhs: 1 }));
+
+    let boost = load::from_bytes(include_bytes!("9.input")).unwrap();
+    assert_eq!(run(compiled::boost::interpret, &boost, 1), Ok(vec![3460311188]));
+    assert_eq!(run(compiled::boost::interpret, &boost, 2), Ok(vec![42202]));
+
+    // day 2 with a patched noun and verb, and with the compiled code
+    // overwritten, which hands over to the interpreter
+    let mut day2 = load::from_bytes(include_bytes!("2.input")).unwrap();
+    day2[1] = 45;
+    day2[2] = 59;
+    assert_eq!(run(compiled::day2::interpret, &day2, 0), Ok(vec![]));
+    day2[4] = 2;
+    assert_eq!(run(compiled::day2::interpret, &day2, 0), Ok(vec![]));
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+
+    let mut variable = vec![];
+    let mut args = std::env::args().skip(1).peekable();
+    while args.peek().map(|arg| arg == "--var") == Some(true) {
+        args.next();
+        variable.push(args.next().ok_or(USAGE)?.parse::<usize>()?);
+    }
+    let path = match (args.next(), args.next()) {
+        (Some(path), None) => path,
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    let prog = load::from_file(&path)?;
+    print!("{}", compile(&prog, &variable));
+    Ok(())
+}
@@ -0,0 +1,60 @@
+mod intcode;
+
+use std::io;
+use std::error::Error;
+use intcode::Machine;
+use intcode::debug::Debugger;
+use intcode::load;
+
+fn session(prog: &[i64], script: &str) -> String {
+    let mut out = vec![];
+    Debugger::new(Machine::new(prog)).repl(script.as_bytes(), &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn run_tests() {
+    // read a number, add 5 to it at address 9, print it
+    let prog = [3,9,1001,9,5,9,4,9,99,0];
+    let out = session(&prog, "c\ni 10\nw 9\nc\nr\nx 8 10\nc\nc\n");
+    assert_eq!(out, concat!(
+        "    0: in [9]\n",
+        "(icdb) waiting for input\n",
+        "    0: in [9]\n",
+        "(icdb) (icdb) (icdb) watchpoint: [9] = 10\n",
+        "    2: add [9], #5, [9]\n",
+        "(icdb) pos 2 relbase 0 input []\n",
+        "(icdb)     8: 99 10\n",
+        "(icdb) watchpoint: [9] = 15\n",
+        "    6: out [9]\n",
+        "(icdb) output: 15\n",
+        "halted\n",
+        "    8: hlt\n",
+        "(icdb) \n"));
+
+    let out = session(&prog, "b 6\ni 1\nc\ns 2\nbogus\nq\n");
+    assert_eq!(out, concat!(
+        "    0: in [9]\n",
+        "(icdb) (icdb) (icdb) breakpoint\n",
+        "    6: out [9]\n",
+        "(icdb) output: 6\n",
+        "halted\n",
+        "    8: hlt\n",
+        "(icdb) unknown command \"bogus\", try help\n",
+        "(icdb) "));
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: intcode_debug PROGRAM");
+            std::process::exit(2);
+        }
+    };
+    let prog = load::from_file(&path)?;
+    let stdout = io::stdout();
+    Debugger::new(Machine::new(&prog)).repl(io::stdin().lock(), &mut stdout.lock())?;
+    Ok(())
+}
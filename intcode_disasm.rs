@@ -0,0 +1,43 @@
+mod intcode;
+
+use std::error::Error;
+use intcode::{ErrorKind, Machine};
+use intcode::disasm::{disassemble, Instruction};
+use intcode::load;
+
+fn run_tests() {
+    assert_eq!(disassemble(&[1102,3,4,11,1105,1,9,42,-1,204,-3,99]),
+               concat!("    0: mul #3, #4, [11]\n",
+                       "    4: jnz #1, #9\n",
+                       "    7: data 42, -1\n",
+                       "    9: out rb-3\n",
+                       "   11: hlt\n"));
+    // unknown opcodes, leftover mode digits and immediate writes are data
+    assert_eq!(disassemble(&[1106,0,4,42,11101,1,1,0,99]),
+               concat!("    0: jz #0, #4\n",
+                       "    3: data 42, 11101, 1, 1, 0, 99\n"));
+
+    // the disassembler takes a word as an instruction exactly when the
+    // machine does
+    for word in 0..30000 {
+        let mem = [word, 0, 0, 0, 99];
+        let mut machine = Machine::new(&mem);
+        machine.push_input(0);
+        let rejected = match machine.step() {
+            Err(err) => matches!(err.kind, ErrorKind::InvalidOpcode(_) | ErrorKind::InvalidMode(_)
+                                           | ErrorKind::ImmediateWrite),
+            Ok(_) => false,
+        };
+        assert_eq!(Instruction::decode(&mem, 0).is_none(), rejected, "word {}", word);
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+    let prog = match std::env::args().nth(1) {
+        Some(path) => load::from_file(&path)?,
+        None => load::from_stdin()?,
+    };
+    print!("{}", disassemble(&prog));
+    Ok(())
+}
@@ -0,0 +1,114 @@
+mod intcode;
+
+use std::error::Error;
+use intcode::{Machine, Memory, Opcode, Event, ErrorKind, IntcodeError};
+use intcode::profile::{Profile, interpret_profiled};
+use intcode::load;
+
+const USAGE: &str = "usage: intcode_profile [--set ADDR=VAL]... [--idle VAL] [--top N] PROGRAM [INPUT]...";
+
+// Profile the machine on its queued input, then on `idle` if given.  Runs
+// out of input with InputExhausted, keeping the profile up to there.
+fn profile_queued(machine: &mut Machine, idle: Option<i64>) -> (Profile, Result<(), IntcodeError>) {
+    let mut profile = Profile::new();
+    loop {
+        match profile.run(machine) {
+            Ok(Event::NeedInput) => match idle {
+                Some(val) => machine.push_input(val),
+                None => {
+                    let err = IntcodeError {
+                        pc: machine.pos,
+                        instruction: machine.mem.read(machine.pos),
+                        kind: ErrorKind::InputExhausted,
+                    };
+                    return (profile, Err(err));
+                }
+            },
+            Ok(Event::Output(_)) => (),
+            Ok(Event::Halted) => return (profile, Ok(())),
+            Err(err) => return (profile, Err(err)),
+        }
+    }
+}
+
+fn run_tests() {
+    // count down from 3, printing each value
+    let mut prog = vec![1101,3,0,14,4,14,1001,14,-1,14,1005,14,4,99,0];
+    let mut outputs = vec![];
+    let profile = interpret_profiled(&mut prog, &mut || 0, &mut |val| outputs.push(val)).unwrap();
+    assert_eq!(outputs, vec![3, 2, 1]);
+    assert_eq!((profile.total, profile.inputs, profile.outputs), (10, 0, 3));
+    assert_eq!(profile.by_opcode[&Opcode::JNZ], 3);
+    assert_eq!(profile.by_addr[&4], 3);
+    assert_eq!(profile.by_addr.get(&13), None);
+    let report = profile.report(&prog, 2);
+    assert!(report.starts_with("instructions executed: 10\ninputs: 0  outputs: 3\n"));
+    assert!(report.ends_with(concat!("hot spots:\n",
+                                     "      4            3  30.00%  out [14]\n",
+                                     "      6            3  30.00%  add [14], #-1, [14]\n")));
+
+    // echo until the input runs out
+    let prog = [3,9,4,9,1105,1,0,99,99,0];
+    let mut machine = Machine::new(&prog);
+    machine.input.extend(&[7, 8]);
+    let (profile, result) = profile_queued(&mut machine, None);
+    assert_eq!(result.unwrap_err().kind, ErrorKind::InputExhausted);
+    assert_eq!((profile.total, profile.inputs, profile.outputs), (6, 2, 2));
+    let mut machine = Machine::new(&[3,9,4,9,99,0,0,0,0,0]);
+    let (profile, result) = profile_queued(&mut machine, Some(4));
+    assert_eq!((result, profile.inputs, profile.outputs), (Ok(()), 1, 1));
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+
+    let mut sets = vec![];
+    let mut idle = None;
+    let mut top = 20;
+    let mut args = std::env::args().skip(1).peekable();
+    while let Some(arg) = args.peek().cloned() {
+        if arg == "--set" || arg == "--idle" || arg == "--top" {
+            args.next();
+            let val = args.peek().cloned().ok_or(USAGE)?;
+            match arg.as_str() {
+                "--set" => {
+                    let mut parts = val.splitn(2, '=');
+                    let addr: usize = parts.next().unwrap().parse()?;
+                    sets.push((addr, parts.next().ok_or(USAGE)?.parse::<i64>()?));
+                }
+                "--idle" => idle = Some(val.parse::<i64>()?),
+                _ => top = val.parse()?,
+            }
+        }
+        else {
+            break;
+        }
+        args.next();
+    }
+    let path = match args.next() {
+        Some(path) => path,
+        None => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    let mut prog = load::from_file(&path)?;
+    for (addr, val) in sets {
+        prog.write(addr, val)
+            .map_err(|_| format!("--set address {} beyond the memory limit", addr))?;
+    }
+    let mut inputs = vec![];
+    for arg in args {
+        inputs.push(arg.parse::<i64>()?);
+    }
+
+    let mut machine = Machine::new(&prog);
+    machine.input.extend(inputs);
+    let (profile, result) = profile_queued(&mut machine, idle);
+    print!("{}", profile.report(&prog, top));
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+    Ok(())
+}
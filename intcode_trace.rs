@@ -0,0 +1,145 @@
+mod intcode;
+
+use std::io;
+use std::error::Error;
+use intcode::{ArithOp, ErrorKind, Event, IntcodeError, Machine, Memory};
+use intcode::trace::{Tracer, TraceError, TraceFormat};
+use intcode::load;
+
+const USAGE: &str = "usage: intcode_trace [--json] [--set ADDR=VAL]... PROGRAM [INPUT]...";
+
+// Trace to completion on the inputs already queued, failing with
+// InputExhausted if the program asks for more.
+fn trace_queued(tracer: &mut Tracer, machine: &mut Machine, write_output: &mut dyn FnMut(i64))
+                -> Result<(), TraceError> {
+    loop {
+        match tracer.run(machine)? {
+            Event::NeedInput => return Err(TraceError::Fault(IntcodeError {
+                pc: machine.pos,
+                instruction: machine.mem.read(machine.pos),
+                kind: ErrorKind::InputExhausted,
+            })),
+            Event::Output(val) => write_output(val),
+            Event::Halted => return Ok(()),
+        }
+    }
+}
+
+fn trace(prog: &[i64], inputs: &[i64], format: TraceFormat)
+         -> (String, Vec<i64>, Result<(), ErrorKind>) {
+    let mut machine = Machine::new(prog);
+    machine.input.extend(inputs);
+    let mut out = vec![];
+    let mut outputs = vec![];
+    let result = trace_queued(&mut Tracer::new(&mut out, format), &mut machine,
+                              &mut |val| outputs.push(val));
+    let result = result.map_err(|err| match err {
+        TraceError::Fault(err) => err.kind,
+        TraceError::Io(err) => panic!("writing to a vec failed: {}", err),
+    });
+    (String::from_utf8(out).unwrap(), outputs, result)
+}
+
+// A trace destination that accepts `room` bytes, then fails like a closed pipe.
+struct ClosedPipe {
+    room: usize,
+}
+
+impl io::Write for ClosedPipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.room == 0 {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed pipe"));
+        }
+        let len = buf.len().min(self.room);
+        self.room -= len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn run_tests() {
+    let prog = [3,9,1001,9,5,9,204,1,99,0];
+    assert_eq!(trace(&prog, &[10], TraceFormat::Text),
+               (concat!("0 in rb=0 -> [9]=10\n",
+                        "2 add rb=0 [9]=10 #5 -> [9]=15\n",
+                        "6 out rb=0 rb+1[1]=9\n").to_string(),
+                vec![9], Ok(())));
+    let prog = [109,10,21101,2,3,-1,209,-5,99];
+    assert_eq!(trace(&prog, &[], TraceFormat::Json).0,
+               concat!(r#"{"pc":0,"op":"arb","rb":0,"operands":[{"mode":"immediate","raw":10,"value":10}],"write":null}"#, "\n",
+                       r#"{"pc":2,"op":"add","rb":10,"operands":[{"mode":"immediate","raw":2,"value":2},{"mode":"immediate","raw":3,"value":3}],"write":{"mode":"relative","raw":-1,"addr":9,"value":5}}"#, "\n",
+                       r#"{"pc":6,"op":"arb","rb":10,"operands":[{"mode":"relative","raw":-5,"addr":5,"value":-1}],"write":null}"#, "\n"));
+
+    // faults end the trace with the machine's own error
+    assert_eq!(trace(&[109,i64::MAX,204,1,99], &[], TraceFormat::Text),
+               (format!("0 arb rb=0 #{}\n", i64::MAX), vec![],
+                Err(ErrorKind::Overflow { op: ArithOp::Add, lhs: i64::MAX, rhs: 1 })));
+    assert_eq!(trace(&[3,0,4,0,3,0,99], &[7], TraceFormat::Text),
+               ("0 in rb=0 -> [0]=7\n2 out rb=0 [0]=7\n".to_string(), vec![7],
+                Err(ErrorKind::InputExhausted)));
+
+    // a failed trace write stops the run with the I/O error
+    let mut pipe = ClosedPipe { room: 20 };
+    let mut machine = Machine::new(&[3,9,1001,9,5,9,204,1,99,0]);
+    machine.push_input(10);
+    let mut outputs = vec![];
+    match trace_queued(&mut Tracer::new(&mut pipe, TraceFormat::Text), &mut machine,
+                       &mut |val| outputs.push(val)) {
+        Err(TraceError::Io(ref err)) => assert_eq!(err.kind(), io::ErrorKind::BrokenPipe),
+        other => panic!("expected a broken pipe, got {:?}", other),
+    }
+    assert_eq!((machine.pos, outputs), (6, vec![]));
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_tests();
+
+    let mut format = TraceFormat::Text;
+    let mut sets = vec![];
+    let mut args = std::env::args().skip(1).peekable();
+    while let Some(arg) = args.peek().cloned() {
+        if arg == "--json" {
+            format = TraceFormat::Json;
+        }
+        else if arg == "--set" {
+            args.next();
+            let assignment = args.peek().cloned().ok_or(USAGE)?;
+            let mut parts = assignment.splitn(2, '=');
+            let addr: usize = parts.next().unwrap().parse()?;
+            let val: i64 = parts.next().ok_or(USAGE)?.parse()?;
+            sets.push((addr, val));
+        }
+        else {
+            break;
+        }
+        args.next();
+    }
+    let path = match args.next() {
+        Some(path) => path,
+        None => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    let mut machine = Machine::new(&load::from_file(&path)?);
+    for (addr, val) in sets {
+        machine.mem.write(addr, val)
+            .map_err(|_| format!("--set address {} beyond the memory limit", addr))?;
+    }
+    for arg in args {
+        machine.push_input(arg.parse()?);
+    }
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let result = trace_queued(&mut Tracer::new(&mut stdout, format), &mut machine,
+                              &mut |val| eprintln!("output: {}", val));
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+    Ok(())
+}